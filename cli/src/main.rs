@@ -0,0 +1,472 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::Result;
+use clap::{Args as ClapArgs, Parser, Subcommand};
+use client::{
+    proof::{decode_journal, decode_proof, is_fake_proof},
+    types::GenerateProofParams,
+    AggregationStatus, AnchorPolicy, DryRunResult, ProofState, ProofStatus, ProvingServiceClient,
+    ReceiptKind, WaitOptions, WorkerActivity,
+};
+use serde::Serialize;
+
+#[derive(Parser, Debug)]
+#[command(about = "Command line client of the EigenDA proving service", long_about = None)]
+struct Cli {
+    /// URL of the proving service JSON RPC server
+    #[arg(short, long, env = "PROVING_SERVICE_URL")]
+    url: String,
+    /// Print the output as JSON, for scripts
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Submit a blob for proving
+    Submit {
+        /// Hex encoded EigenDA blob key
+        blob_id: String,
+        /// Hex encoded ABI EigenDACert of the blob, so the service doesn't request it from the disperser
+        #[arg(long)]
+        eigenda_cert: Option<String>,
+        /// File with the raw payload of the blob, proven instead of retrieving it from the relays
+        #[arg(long, requires = "eigenda_cert")]
+        payload_file: Option<PathBuf>,
+        /// Kind of receipt to generate (composite, succinct, groth16), groth16 if not set
+        #[arg(long)]
+        receipt_kind: Option<ReceiptKind>,
+        /// Block the cert validity is proven at (latest, latest-<N>, <block number> or reference),
+        /// the service default if not set
+        #[arg(long)]
+        anchor_policy: Option<AnchorPolicy>,
+        #[command(flatten)]
+        wait: WaitArgs,
+    },
+    /// Run the guest for a blob without proving it, to check if it would pass
+    DryRun {
+        /// Hex encoded EigenDA blob key
+        blob_id: String,
+        /// Hex encoded ABI EigenDACert of the blob, so the service doesn't request it from the disperser
+        #[arg(long)]
+        eigenda_cert: Option<String>,
+        /// File with the raw payload of the blob, used instead of retrieving it from the relays
+        #[arg(long, requires = "eigenda_cert")]
+        payload_file: Option<PathBuf>,
+    },
+    /// Show the status of a proof request
+    Status {
+        /// Hex encoded EigenDA blob key
+        blob_id: String,
+    },
+    /// Fetch the proof of a blob
+    Get {
+        /// Hex encoded EigenDA blob key
+        blob_id: String,
+        /// Decode the proof into its seal, image ID and journal
+        #[arg(long)]
+        decode: bool,
+        #[command(flatten)]
+        wait: WaitArgs,
+    },
+    /// List proof requests, oldest first
+    List {
+        /// Only list the proof requests in this state (queued, proven, failed, cancelled)
+        #[arg(long)]
+        state: Option<ProofState>,
+    },
+    /// Put a failed, cancelled or fake proof request back in the queue
+    Retry {
+        /// Hex encoded EigenDA blob key
+        blob_id: String,
+    },
+    /// Compress again the stored receipt of a proven request, without proving the guest again
+    Recompress {
+        /// Hex encoded EigenDA blob key
+        blob_id: String,
+    },
+    /// Cancel a queued proof request
+    Cancel {
+        /// Hex encoded EigenDA blob key
+        blob_id: String,
+    },
+    /// Mark the proof of a proven request as consumed, so it's not re-proven when it expires
+    Consume {
+        /// Hex encoded EigenDA blob key
+        blob_id: String,
+    },
+    /// Export the guest input of a proof request, to replay it offline with the replay binary
+    ExportInput {
+        /// Hex encoded EigenDA blob key
+        blob_id: String,
+        /// File the bundle is written to, instead of the standard output
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Aggregate the proofs of many proven blobs into a single proof
+    Aggregate {
+        /// Hex encoded EigenDA blob keys, in the order they are committed
+        #[arg(required = true)]
+        blob_ids: Vec<String>,
+    },
+    /// Show the status of an aggregation request, with its proof once generated
+    Aggregation {
+        /// ID returned by the aggregate command
+        aggregation_id: u64,
+    },
+    /// Print the configuration the service generates its proofs with
+    Info {
+        /// Fail if the service guest image ID is not this one (hex encoded)
+        #[arg(long)]
+        expected_image_id: Option<String>,
+    },
+    /// Control the proof generation worker through the admin methods
+    Admin {
+        /// URL of the proving service admin JSON RPC server (its ADMIN_URL)
+        #[arg(long, env = "PROVING_SERVICE_ADMIN_URL")]
+        admin_url: String,
+        #[command(subcommand)]
+        command: AdminCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum AdminCommand {
+    /// Stop taking new proof requests, the one being proven is still finished
+    Pause,
+    /// Resume taking proof requests
+    Resume,
+    /// Finish the proof request being proven, and then pause
+    Drain,
+    /// Show the worker state and the proof request being proven
+    Activity,
+}
+
+#[derive(ClapArgs, Debug)]
+struct WaitArgs {
+    /// Wait until the proof is generated
+    #[arg(long)]
+    wait: bool,
+    /// Maximum seconds each wait request is held by the service before asking again
+    #[arg(long, default_value_t = 30)]
+    poll_interval: u64,
+    /// Maximum seconds to wait for the proof, waits forever if not set
+    #[arg(long)]
+    timeout: Option<u64>,
+    /// Accept fake dev mode proofs, which don't verify onchain
+    #[arg(long)]
+    allow_fake: bool,
+}
+
+impl WaitArgs {
+    fn options(&self) -> WaitOptions {
+        WaitOptions {
+            poll_interval: Duration::from_secs(self.poll_interval),
+            timeout: self.timeout.map(Duration::from_secs),
+            allow_fake: self.allow_fake,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ProofOutput {
+    blob_id: String,
+    proof: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    fake: bool,
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn print_status(status: &ProofStatus, json: bool) -> Result<()> {
+    if json {
+        return print_json(status);
+    }
+    let fake = if status.fake { "\t(fake)" } else { "" };
+    let anchor_block = status
+        .anchor_block
+        .map(|anchor_block| format!(" at block {}", anchor_block))
+        .unwrap_or_default();
+    let freshness = match (status.freshness, status.expiry_block) {
+        (Some(freshness), Some(expiry_block)) => {
+            format!("\t{} (expires at block {})", freshness, expiry_block)
+        }
+        _ => String::new(),
+    };
+    let consumed = if status.consumed { "\t(consumed)" } else { "" };
+    let reorgs = match status.reorgs {
+        0 => String::new(),
+        reorgs => format!("\t({} reorgs)", reorgs),
+    };
+    println!(
+        "{}\t{}\t{}\tanchor {}{}{}{}{}{}",
+        status.blob_id,
+        status.state,
+        status.receipt_kind,
+        status.anchor_policy,
+        anchor_block,
+        freshness,
+        consumed,
+        reorgs,
+        fake
+    );
+    Ok(())
+}
+
+fn print_aggregation(status: &AggregationStatus, json: bool) -> Result<()> {
+    if json {
+        return print_json(status);
+    }
+    let fake = if status.fake { "\t(fake)" } else { "" };
+    println!(
+        "{}\t{}\t{} blobs{}",
+        status.aggregation_id,
+        status.state,
+        status.blob_ids.len(),
+        fake
+    );
+    if let Some(proof) = &status.proof {
+        println!("{}", proof);
+    }
+    Ok(())
+}
+
+async fn get_proof(
+    client: &ProvingServiceClient,
+    blob_id: &str,
+    decode: bool,
+    wait: &WaitArgs,
+    json: bool,
+) -> Result<()> {
+    let proof = if wait.wait {
+        client.wait_for_proof(blob_id, &wait.options()).await?
+    } else {
+        client.get_proof(blob_id).await?
+    };
+    let fake = is_fake_proof(&proof);
+    if fake {
+        eprintln!(
+            "Warning: the proof of {} is a fake dev mode proof, it doesn't verify onchain",
+            blob_id
+        );
+    }
+
+    if decode {
+        let decoded = decode_proof(&proof)?;
+        if json {
+            return print_json(&decoded);
+        }
+        println!("seal:           {}", decoded.seal);
+        println!("fake:           {}", decoded.fake);
+        println!("image_id:       {}", decoded.image_id);
+        println!("hash:           {}", decoded.journal.hash);
+        println!("env_commitment: {}", decoded.journal.env_commitment);
+        println!("kzg_proof:      {}", decoded.journal.kzg_proof);
+        println!("chain_id:       {}", decoded.journal.chain_id);
+        return Ok(());
+    }
+
+    let proof = hex::encode(proof);
+    if json {
+        return print_json(&ProofOutput {
+            blob_id: blob_id.to_string(),
+            proof,
+            fake,
+        });
+    }
+    println!("{}", proof);
+    Ok(())
+}
+
+fn print_dry_run(result: &DryRunResult, json: bool) -> Result<()> {
+    if json {
+        print_json(result)?;
+    } else {
+        let outcome = if result.passed { "passed" } else { "failed" };
+        println!("{}\t{}", result.blob_id, outcome);
+        if let Some(error) = &result.error {
+            println!("error:          {}", error);
+        }
+        if let (Some(total_cycles), Some(user_cycles), Some(segments)) =
+            (result.total_cycles, result.user_cycles, result.segments)
+        {
+            println!("total_cycles:   {}", total_cycles);
+            println!("user_cycles:    {}", user_cycles);
+            println!("segments:       {}", segments);
+        }
+        if let Some(journal) = &result.journal {
+            println!("journal:        {}", journal);
+            if let Ok(decoded) = decode_journal(&hex::decode(journal)?) {
+                println!("hash:           {}", decoded.hash);
+                println!("env_commitment: {}", decoded.env_commitment);
+                println!("kzg_proof:      {}", decoded.kzg_proof);
+                println!("chain_id:       {}", decoded.chain_id);
+            }
+        }
+    }
+    if !result.passed {
+        anyhow::bail!("Dry run failed for {}", result.blob_id);
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let client = ProvingServiceClient::new(cli.url);
+
+    match cli.command {
+        Command::Submit {
+            blob_id,
+            eigenda_cert,
+            payload_file,
+            receipt_kind,
+            anchor_policy,
+            wait,
+        } => {
+            let payload = payload_file.map(std::fs::read).transpose()?;
+            client
+                .generate_proof_with_params(GenerateProofParams {
+                    blob_id: blob_id.clone(),
+                    eigenda_cert,
+                    payload: payload.map(hex::encode),
+                    receipt_kind,
+                    anchor_policy,
+                })
+                .await?;
+            if wait.wait {
+                get_proof(&client, &blob_id, false, &wait, cli.json).await?;
+            } else {
+                let status = client.get_proof_status(&blob_id).await?;
+                print_status(&status, cli.json)?;
+            }
+        }
+        Command::DryRun {
+            blob_id,
+            eigenda_cert,
+            payload_file,
+        } => {
+            let eigenda_cert = eigenda_cert
+                .map(|eigenda_cert| hex::decode(eigenda_cert.trim_start_matches("0x")))
+                .transpose()?;
+            let payload = payload_file.map(std::fs::read).transpose()?;
+            let result = client
+                .dry_run(&blob_id, eigenda_cert.as_deref(), payload.as_deref())
+                .await?;
+            print_dry_run(&result, cli.json)?;
+        }
+        Command::Status { blob_id } => {
+            let status = client.get_proof_status(&blob_id).await?;
+            print_status(&status, cli.json)?;
+        }
+        Command::Get {
+            blob_id,
+            decode,
+            wait,
+        } => get_proof(&client, &blob_id, decode, &wait, cli.json).await?,
+        Command::List { state } => {
+            let proofs = client.list_proofs(state).await?;
+            if cli.json {
+                print_json(&proofs)?;
+            } else {
+                for status in &proofs {
+                    print_status(status, false)?;
+                }
+            }
+        }
+        Command::Retry { blob_id } => {
+            let status = client.retry_proof(&blob_id).await?;
+            print_status(&status, cli.json)?;
+        }
+        Command::Recompress { blob_id } => {
+            let status = client.recompress_proof(&blob_id).await?;
+            print_status(&status, cli.json)?;
+        }
+        Command::Cancel { blob_id } => {
+            let status = client.cancel_proof(&blob_id).await?;
+            print_status(&status, cli.json)?;
+        }
+        Command::Consume { blob_id } => {
+            let status = client.mark_proof_consumed(&blob_id).await?;
+            print_status(&status, cli.json)?;
+        }
+        Command::ExportInput { blob_id, output } => {
+            let bundle = client.export_guest_input(&blob_id).await?;
+            let bundle = serde_json::to_string_pretty(&bundle)?;
+            match output {
+                Some(output) => std::fs::write(output, bundle)?,
+                None => println!("{}", bundle),
+            }
+        }
+        Command::Aggregate { blob_ids } => {
+            let status = client.aggregate_proofs(&blob_ids).await?;
+            print_aggregation(&status, cli.json)?;
+        }
+        Command::Aggregation { aggregation_id } => {
+            let status = client.get_aggregation_status(aggregation_id).await?;
+            print_aggregation(&status, cli.json)?;
+        }
+        Command::Info { expected_image_id } => {
+            let info = match expected_image_id {
+                Some(expected) => client.ensure_image_id(&expected).await?,
+                None => client.get_service_info().await?,
+            };
+            if cli.json {
+                print_json(&info)?;
+            } else {
+                println!("version:                   {}", info.version);
+                println!("image_id:                  {}", info.image_id);
+                println!("aggregator_image_id:       {}", info.aggregator_image_id);
+                println!("risc0_version:             {}", info.risc0_version);
+                println!("verifier_parameters:       {}", info.verifier_parameters);
+                println!("verifier_selector:         {}", info.verifier_selector);
+                println!(
+                    "chain:                     {} ({})",
+                    info.chain.name, info.chain.chain_id
+                );
+                println!(
+                    "cert_verifier_router_addr: {}",
+                    info.cert_verifier_router_addr
+                );
+                println!("payload_form:              {}", info.payload_form);
+                println!("anchor_policy:             {}", info.anchor_policy);
+                println!("env_commitment:            {}", info.env_commitment);
+                println!("blob_version:              {}", info.blob_version);
+                println!(
+                    "srs:                       {} (order {}, {} points loaded)",
+                    info.srs.path, info.srs.order, info.srs.points_loaded
+                );
+                println!("dev_mode:                  {}", info.dev_mode);
+            }
+        }
+        Command::Admin { admin_url, command } => {
+            let admin = ProvingServiceClient::new(admin_url);
+            let activity = match command {
+                AdminCommand::Pause => admin.pause_worker().await?,
+                AdminCommand::Resume => admin.resume_worker().await?,
+                AdminCommand::Drain => admin.drain_worker().await?,
+                AdminCommand::Activity => admin.get_worker_activity().await?,
+            };
+            print_activity(&activity, cli.json)?;
+        }
+    }
+    Ok(())
+}
+
+fn print_activity(activity: &WorkerActivity, json: bool) -> Result<()> {
+    if json {
+        return print_json(activity);
+    }
+    match (&activity.current_blob_id, activity.current_elapsed_secs) {
+        (Some(blob_id), Some(elapsed)) => {
+            println!("{}, proving {} for {}s", activity.state, blob_id, elapsed)
+        }
+        _ => println!("{}, idle", activity.state),
+    }
+    Ok(())
+}
@@ -0,0 +1,396 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::json;
+use tokio::time::Instant;
+
+use crate::error::ClientError;
+use crate::types::{
+    AggregateProofsParams, AggregationIdParams, AggregationStatus, DryRunResult,
+    GenerateProofParams, ListProofsParams, ProofState, ProofStatus, ServiceInfo,
+    WaitForProofParams, WorkerActivity,
+};
+
+/// How [`ProvingServiceClient::wait_for_proof`] waits for the proof.
+#[derive(Debug, Clone)]
+pub struct WaitOptions {
+    /// Maximum time each `wait_for_proof` request is held by the service
+    /// before the client asks again.
+    pub poll_interval: Duration,
+    /// Maximum time to wait for the proof, `None` waits forever.
+    pub timeout: Option<Duration>,
+    /// Whether fake dev mode proofs are returned, instead of failing with
+    /// [`ClientError::InvalidState`]. They don't verify onchain.
+    pub allow_fake: bool,
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(30),
+            timeout: None,
+            allow_fake: false,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<R> {
+    result: Option<R>,
+    error: Option<RpcError>,
+}
+
+/// Client of the proving service JSON RPC API.
+pub struct ProvingServiceClient {
+    url: String,
+    http: reqwest::Client,
+    next_id: AtomicU64,
+}
+
+impl ProvingServiceClient {
+    /// Creates a client for the proving service running on the given URL.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_http_client(url, reqwest::Client::new())
+    }
+
+    /// Creates a client that sends its requests through the given HTTP client.
+    pub fn with_http_client(url: impl Into<String>, http: reqwest::Client) -> Self {
+        Self {
+            url: url.into(),
+            http,
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Calls a JSON RPC method of the service and decodes its result.
+    pub async fn call<P: Serialize, R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<R, ClientError> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": self.next_id.fetch_add(1, Ordering::Relaxed),
+        });
+        let response: RpcResponse<R> = self
+            .http
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        match (response.result, response.error) {
+            (_, Some(error)) => Err(ClientError::from_rpc_error(error.code, error.message)),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(ClientError::InvalidResponse(format!(
+                "{} response has neither result nor error",
+                method
+            ))),
+        }
+    }
+
+    /// Requests the generation of a proof for the blob.
+    pub async fn generate_proof(&self, blob_id: &str) -> Result<(), ClientError> {
+        let _: String = self.call("generate_proof", blob_id_params(blob_id)).await?;
+        Ok(())
+    }
+
+    /// Requests the generation of a proof for the blob, for the given ABI encoded
+    /// `EigenDACert`, so that the service doesn't need to request it from the disperser.
+    pub async fn generate_proof_with_cert(
+        &self,
+        blob_id: &str,
+        eigenda_cert: &[u8],
+    ) -> Result<(), ClientError> {
+        let params = GenerateProofParams {
+            blob_id: blob_id.to_string(),
+            eigenda_cert: Some(hex::encode(eigenda_cert)),
+            payload: None,
+            receipt_kind: None,
+            anchor_policy: None,
+        };
+        let _: String = self.call("generate_proof", params).await?;
+        Ok(())
+    }
+
+    /// Requests the generation of a proof for the blob, uploading both its ABI encoded
+    /// `EigenDACert` and its payload, so that the service needs neither the disperser
+    /// nor the relays. The payload is checked against the cert blob commitment before proving.
+    pub async fn generate_proof_with_payload(
+        &self,
+        blob_id: &str,
+        eigenda_cert: &[u8],
+        payload: &[u8],
+    ) -> Result<(), ClientError> {
+        let params = GenerateProofParams {
+            blob_id: blob_id.to_string(),
+            eigenda_cert: Some(hex::encode(eigenda_cert)),
+            payload: Some(hex::encode(payload)),
+            receipt_kind: None,
+            anchor_policy: None,
+        };
+        let _: String = self.call("generate_proof", params).await?;
+        Ok(())
+    }
+
+    /// Requests the generation of a proof with all the `generate_proof` params, like the
+    /// kind of receipt to generate.
+    pub async fn generate_proof_with_params(
+        &self,
+        params: GenerateProofParams,
+    ) -> Result<(), ClientError> {
+        let _: String = self.call("generate_proof", params).await?;
+        Ok(())
+    }
+
+    /// Runs the guest for the blob in the executor, without proving it nor queuing a proof
+    /// request, to check if it would pass. The ABI encoded `EigenDACert` and the payload are
+    /// used if given, like in [`ProvingServiceClient::generate_proof_with_payload`].
+    pub async fn dry_run(
+        &self,
+        blob_id: &str,
+        eigenda_cert: Option<&[u8]>,
+        payload: Option<&[u8]>,
+    ) -> Result<DryRunResult, ClientError> {
+        let params = GenerateProofParams {
+            blob_id: blob_id.to_string(),
+            eigenda_cert: eigenda_cert.map(hex::encode),
+            payload: payload.map(hex::encode),
+            receipt_kind: None,
+            anchor_policy: None,
+        };
+        self.call("dry_run", params).await
+    }
+
+    /// Returns the ABI encoded proof of the blob.
+    /// Fails with [`ClientError::ProofQueued`] if it is not generated yet.
+    pub async fn get_proof(&self, blob_id: &str) -> Result<Vec<u8>, ClientError> {
+        let proof: String = self.call("get_proof", blob_id_params(blob_id)).await?;
+        decode_proof(&proof)
+    }
+
+    /// Returns the ABI encoded proof of the blob, or `None` if it is not generated yet.
+    pub async fn try_get_proof(&self, blob_id: &str) -> Result<Option<Vec<u8>>, ClientError> {
+        match self.get_proof(blob_id).await {
+            Ok(proof) => Ok(Some(proof)),
+            Err(ClientError::ProofQueued) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns the current status of the proof request for the blob.
+    pub async fn get_proof_status(&self, blob_id: &str) -> Result<ProofStatus, ClientError> {
+        self.call("get_proof_status", blob_id_params(blob_id)).await
+    }
+
+    /// Lists the proof requests, oldest first, optionally filtered by state.
+    pub async fn list_proofs(
+        &self,
+        state: Option<ProofState>,
+    ) -> Result<Vec<ProofStatus>, ClientError> {
+        self.call("list_proofs", ListProofsParams { state }).await
+    }
+
+    /// Cancels a queued proof request.
+    pub async fn cancel_proof(&self, blob_id: &str) -> Result<ProofStatus, ClientError> {
+        self.call("cancel_proof", blob_id_params(blob_id)).await
+    }
+
+    /// Puts a failed, cancelled or fake proof request back in the queue.
+    pub async fn retry_proof(&self, blob_id: &str) -> Result<ProofStatus, ClientError> {
+        self.call("retry_proof", blob_id_params(blob_id)).await
+    }
+
+    /// Puts a proven request back in the queue to compress its stored receipt into a new proof,
+    /// without proving the guest again.
+    pub async fn recompress_proof(&self, blob_id: &str) -> Result<ProofStatus, ClientError> {
+        self.call("recompress_proof", blob_id_params(blob_id)).await
+    }
+
+    /// Marks the proof of a proven request as consumed, so it's not re-proven when it expires.
+    pub async fn mark_proof_consumed(&self, blob_id: &str) -> Result<ProofStatus, ClientError> {
+        self.call("mark_proof_consumed", blob_id_params(blob_id))
+            .await
+    }
+
+    /// Returns the self-contained guest input bundle of the proof request, to replay it offline.
+    /// The bundle format is defined by the service, so it's returned as raw JSON.
+    pub async fn export_guest_input(
+        &self,
+        blob_id: &str,
+    ) -> Result<serde_json::Value, ClientError> {
+        self.call("export_guest_input", blob_id_params(blob_id))
+            .await
+    }
+
+    /// Requests the aggregation of the proofs of the given proven blobs into a single proof.
+    pub async fn aggregate_proofs(
+        &self,
+        blob_ids: &[String],
+    ) -> Result<AggregationStatus, ClientError> {
+        let params = AggregateProofsParams {
+            blob_ids: blob_ids.to_vec(),
+        };
+        self.call("aggregate_proofs", params).await
+    }
+
+    /// Returns the status of an aggregation request, including its proof once generated.
+    pub async fn get_aggregation_status(
+        &self,
+        aggregation_id: u64,
+    ) -> Result<AggregationStatus, ClientError> {
+        self.call(
+            "get_aggregation_status",
+            AggregationIdParams { aggregation_id },
+        )
+        .await
+    }
+
+    /// Returns the configuration the service generates its proofs with.
+    pub async fn get_service_info(&self) -> Result<ServiceInfo, ClientError> {
+        self.call("get_service_info", ()).await
+    }
+
+    /// Fails with [`ClientError::ImageIdMismatch`] if the service proves a
+    /// different guest than the one with the given (hex encoded) image ID.
+    pub async fn ensure_image_id(&self, expected: &str) -> Result<ServiceInfo, ClientError> {
+        let info = self.get_service_info().await?;
+        let expected = expected.trim_start_matches("0x").to_lowercase();
+        if info.image_id != expected {
+            return Err(ClientError::ImageIdMismatch {
+                expected,
+                actual: info.image_id,
+            });
+        }
+        Ok(info)
+    }
+
+    /// Waits server side until the proof request for the blob leaves the queue
+    /// or the timeout elapses, returning its status (still queued on timeout).
+    pub async fn wait_for_proof_status(
+        &self,
+        blob_id: &str,
+        timeout: Duration,
+    ) -> Result<ProofStatus, ClientError> {
+        let params = WaitForProofParams {
+            blob_id: blob_id.to_string(),
+            timeout_secs: Some(timeout.as_secs().max(1)),
+        };
+        self.call("wait_for_proof", params).await
+    }
+
+    /// Waits until the proof of the blob is generated, its generation fails,
+    /// or the timeout elapses. The service holds each request until the
+    /// proof request leaves the queue, for at most `poll_interval`.
+    /// Fake dev mode proofs are refused unless [`WaitOptions::allow_fake`] is set.
+    pub async fn wait_for_proof(
+        &self,
+        blob_id: &str,
+        options: &WaitOptions,
+    ) -> Result<Vec<u8>, ClientError> {
+        let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            let wait = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        return Err(ClientError::Timeout);
+                    }
+                    remaining.min(options.poll_interval)
+                }
+                None => options.poll_interval,
+            };
+            let status = self.wait_for_proof_status(blob_id, wait).await?;
+            let fake = status.fake;
+            match (status.state, status.proof) {
+                (ProofState::Failed, _) => return Err(ClientError::ProofGenerationFailed),
+                (ProofState::Proven, _) if fake && !options.allow_fake => {
+                    return Err(ClientError::InvalidState(
+                        "Proof for Blob ID is a fake dev mode proof".to_string(),
+                    ))
+                }
+                (ProofState::Cancelled, _) => {
+                    return Err(ClientError::InvalidState(
+                        "Proof request for Blob ID was cancelled".to_string(),
+                    ))
+                }
+                (ProofState::Proven, Some(proof)) => return decode_proof(&proof),
+                // Only fake proofs are withheld, by services not running in dev mode
+                (ProofState::Proven, None) => {
+                    return Err(ClientError::InvalidState(
+                        "Proof for Blob ID is a fake dev mode proof, and the service is not in dev mode"
+                            .to_string(),
+                    ))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Pauses the proof generation worker. It stops taking new proof requests, but still
+    /// finishes the current one.
+    /// Admin method, the client must point to the service `ADMIN_URL`.
+    pub async fn pause_worker(&self) -> Result<WorkerActivity, ClientError> {
+        self.call("admin_pause", ()).await
+    }
+
+    /// Resumes the proof generation worker.
+    /// Admin method, the client must point to the service `ADMIN_URL`.
+    pub async fn resume_worker(&self) -> Result<WorkerActivity, ClientError> {
+        self.call("admin_resume", ()).await
+    }
+
+    /// Lets the proof generation worker finish its current proof request, and then pauses it.
+    /// Admin method, the client must point to the service `ADMIN_URL`.
+    pub async fn drain_worker(&self) -> Result<WorkerActivity, ClientError> {
+        self.call("admin_drain", ()).await
+    }
+
+    /// Returns the state of the proof generation worker and what it is proving.
+    /// Admin method, the client must point to the service `ADMIN_URL`.
+    pub async fn get_worker_activity(&self) -> Result<WorkerActivity, ClientError> {
+        self.call("admin_worker_activity", ()).await
+    }
+
+    /// Requests the generation of a proof for the blob and waits for it.
+    /// A blob that was already submitted is waited on instead of failing.
+    pub async fn generate_and_wait(
+        &self,
+        blob_id: &str,
+        options: &WaitOptions,
+    ) -> Result<Vec<u8>, ClientError> {
+        match self.generate_proof(blob_id).await {
+            Ok(()) | Err(ClientError::AlreadySubmitted) => {}
+            Err(e) => return Err(e),
+        }
+        self.wait_for_proof(blob_id, options).await
+    }
+}
+
+fn blob_id_params(blob_id: &str) -> GenerateProofParams {
+    GenerateProofParams {
+        blob_id: blob_id.to_string(),
+        eigenda_cert: None,
+        payload: None,
+        receipt_kind: None,
+        anchor_policy: None,
+    }
+}
+
+fn decode_proof(proof: &str) -> Result<Vec<u8>, ClientError> {
+    hex::decode(proof)
+        .map_err(|e| ClientError::InvalidResponse(format!("Proof is not valid hex: {}", e)))
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+
+// The JSON RPC Specification defines for the Server error (Reserved for implementation-defined server-errors) the range of codes -32000 to -32099
+/// The proof request exists but its proof is not generated yet.
+pub const PROOF_NOT_FOUND_ERROR: i64 = -32001;
+/// The proof request is not in a state that allows the operation (e.g. cancelling a proven request).
+pub const PROOF_REQUEST_INVALID_STATE_ERROR: i64 = -32005;
+
+// Codes defined by the JSON RPC Specification
+pub const INVALID_PARAMS_ERROR: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+
+// The sequencer integration tells these errors apart by their code, so they keep the generic
+// codes they were first returned with, and are told apart from other errors by their message.
+/// Message of the `INVALID_PARAMS_ERROR` returned when the proof generation for the blob ID failed.
+pub const PROOF_GENERATION_FAILED_MESSAGE: &str = "Proof request for Blob ID was not valid";
+/// Message of the `INVALID_PARAMS_ERROR` returned when a proof request for the blob ID was
+/// already submitted.
+pub const ALREADY_SUBMITTED_MESSAGE: &str = "Blob ID already submitted";
+/// Message of the `INTERNAL_ERROR` returned when no proof request was submitted for the blob ID.
+pub const PROOF_REQUEST_NOT_FOUND_MESSAGE: &str = "Proof request for Blob ID not found";
+/// Message of the `INTERNAL_ERROR` returned when there is no aggregation request with the ID.
+pub const AGGREGATION_NOT_FOUND_MESSAGE: &str = "Aggregation request not found";
+
+/// Errors returned by the [`ProvingServiceClient`](crate::ProvingServiceClient).
+#[derive(Debug)]
+pub enum ClientError {
+    /// The proof is not generated yet.
+    ProofQueued,
+    /// No proof request was submitted for the blob ID.
+    ProofRequestNotFound,
+    /// The proof generation for the blob ID failed.
+    ProofGenerationFailed,
+    /// A proof request for the blob ID was already submitted.
+    AlreadySubmitted,
+    /// No aggregation request exists with the ID.
+    AggregationNotFound,
+    /// The proof request is not in a state that allows the operation.
+    InvalidState(String),
+    /// The server rejected the request parameters.
+    InvalidParams(String),
+    /// The server failed handling the request.
+    Internal(String),
+    /// The server returned an error with a code this client doesn't know about.
+    Rpc { code: i64, message: String },
+    /// The request could not be sent, or the response could not be read.
+    Transport(reqwest::Error),
+    /// The server response is not a valid JSON RPC response for the method.
+    InvalidResponse(String),
+    /// The proof was not ready before the wait timeout elapsed.
+    Timeout,
+    /// The service generates proofs for a different guest than the expected one.
+    ImageIdMismatch { expected: String, actual: String },
+}
+
+impl ClientError {
+    /// Builds the error matching a JSON RPC error returned by the server.
+    pub fn from_rpc_error(code: i64, message: String) -> Self {
+        match (code, message.as_str()) {
+            (PROOF_NOT_FOUND_ERROR, _) => ClientError::ProofQueued,
+            (PROOF_REQUEST_INVALID_STATE_ERROR, _) => ClientError::InvalidState(message),
+            (INVALID_PARAMS_ERROR, PROOF_GENERATION_FAILED_MESSAGE) => {
+                ClientError::ProofGenerationFailed
+            }
+            (INVALID_PARAMS_ERROR, ALREADY_SUBMITTED_MESSAGE) => ClientError::AlreadySubmitted,
+            (INVALID_PARAMS_ERROR, _) => ClientError::InvalidParams(message),
+            (INTERNAL_ERROR, PROOF_REQUEST_NOT_FOUND_MESSAGE) => ClientError::ProofRequestNotFound,
+            (INTERNAL_ERROR, AGGREGATION_NOT_FOUND_MESSAGE) => ClientError::AggregationNotFound,
+            (INTERNAL_ERROR, _) => ClientError::Internal(message),
+            _ => ClientError::Rpc { code, message },
+        }
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::ProofQueued => write!(f, "Proof not found (still queued)"),
+            ClientError::ProofRequestNotFound => write!(f, "{}", PROOF_REQUEST_NOT_FOUND_MESSAGE),
+            ClientError::ProofGenerationFailed => write!(f, "Proof generation for Blob ID failed"),
+            ClientError::AlreadySubmitted => write!(f, "{}", ALREADY_SUBMITTED_MESSAGE),
+            ClientError::AggregationNotFound => write!(f, "{}", AGGREGATION_NOT_FOUND_MESSAGE),
+            ClientError::InvalidState(message) => write!(f, "{}", message),
+            ClientError::InvalidParams(message) => write!(f, "Invalid params: {}", message),
+            ClientError::Internal(message) => write!(f, "Internal error: {}", message),
+            ClientError::Rpc { code, message } => write!(f, "RPC error {}: {}", code, message),
+            ClientError::Transport(e) => write!(f, "Transport error: {}", e),
+            ClientError::InvalidResponse(message) => write!(f, "Invalid response: {}", message),
+            ClientError::Timeout => write!(f, "Timed out waiting for the proof"),
+            ClientError::ImageIdMismatch { expected, actual } => write!(
+                f,
+                "Proving service image ID {} does not match the expected {}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClientError::Transport(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        ClientError::Transport(err)
+    }
+}
@@ -0,0 +1,11 @@
+pub mod client;
+pub mod error;
+pub mod proof;
+pub mod types;
+
+pub use client::{ProvingServiceClient, WaitOptions};
+pub use error::ClientError;
+pub use types::{
+    AggregationStatus, AnchorPolicy, DryRunResult, ExecutionStats, ProofState, ProofStats,
+    ProofStatus, ReceiptKind, ServiceInfo, WorkerActivity, WorkerState,
+};
@@ -0,0 +1,110 @@
+use ethabi::{ParamType, Token};
+use serde::Serialize;
+
+/// Selector of the seal of fake dev mode proofs, in place of the Groth16 verifier selector.
+/// It is followed by the digest of the receipt claim, as expected by the risc0 mock verifier.
+pub const FAKE_PROOF_SELECTOR: [u8; 4] = *b"FAKE";
+
+/// Proof returned by the proving service, decoded from its ABI encoding.
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedProof {
+    /// Groth16 verifier selector followed by the seal, hex encoded. For `composite` and
+    /// `succinct` proofs it is the JSON encoded risc0 receipt, verified offchain.
+    pub seal: String,
+    /// Whether it is a fake dev mode proof, which doesn't verify onchain.
+    pub fake: bool,
+    /// Image ID of the guest program, hex encoded.
+    pub image_id: String,
+    /// Journal committed by the guest.
+    pub journal: DecodedJournal,
+}
+
+/// Public outputs committed by the guest.
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedJournal {
+    /// Keccak256 hash of the blob payload, hex encoded.
+    pub hash: String,
+    /// ABI encoded risc0 steel environment commitment, hex encoded.
+    pub env_commitment: String,
+    /// Compressed KZG proof that the cert commitment commits to the blob, hex encoded.
+    pub kzg_proof: String,
+    /// ID of the chain the EigenDA cert was verified against.
+    pub chain_id: u64,
+}
+
+/// Decodes an ABI encoded proof tuple `(bytes seal, bytes32 image_id, bytes journal)`.
+pub fn decode_proof(proof: &[u8]) -> Result<DecodedProof, ethabi::Error> {
+    let raw = decode_raw_proof(proof)?;
+    Ok(DecodedProof {
+        fake: raw.seal.starts_with(&FAKE_PROOF_SELECTOR),
+        seal: hex::encode(raw.seal),
+        image_id: hex::encode(raw.image_id),
+        journal: decode_journal(&raw.journal)?,
+    })
+}
+
+/// Checks if an ABI encoded proof tuple is a fake dev mode proof, see [`FAKE_PROOF_SELECTOR`].
+pub fn is_fake_proof(proof: &[u8]) -> bool {
+    decode_raw_proof(proof).is_ok_and(|raw| raw.seal.starts_with(&FAKE_PROOF_SELECTOR))
+}
+
+/// Fields of the ABI encoded proof tuple.
+struct RawProof {
+    seal: Vec<u8>,
+    image_id: Vec<u8>,
+    journal: Vec<u8>,
+}
+
+fn decode_raw_proof(proof: &[u8]) -> Result<RawProof, ethabi::Error> {
+    let mut outer = ethabi::decode(
+        &[ParamType::Tuple(vec![
+            ParamType::Bytes,
+            ParamType::FixedBytes(32),
+            ParamType::Bytes,
+        ])],
+        proof,
+    )?;
+    let Some(Token::Tuple(fields)) = outer.pop() else {
+        return Err(ethabi::Error::InvalidData);
+    };
+    let [Token::Bytes(seal), Token::FixedBytes(image_id), Token::Bytes(journal)] = &fields[..]
+    else {
+        return Err(ethabi::Error::InvalidData);
+    };
+    Ok(RawProof {
+        seal: seal.clone(),
+        image_id: image_id.clone(),
+        journal: journal.clone(),
+    })
+}
+
+/// Decodes the ABI encoded journal `(bytes32 hash, bytes env_commitment, bytes proof, uint64 chain_id)`.
+pub fn decode_journal(journal: &[u8]) -> Result<DecodedJournal, ethabi::Error> {
+    let mut outer = ethabi::decode(
+        &[ParamType::Tuple(vec![
+            ParamType::FixedBytes(32),
+            ParamType::Bytes,
+            ParamType::Bytes,
+            ParamType::Uint(64),
+        ])],
+        journal,
+    )?;
+    let Some(Token::Tuple(fields)) = outer.pop() else {
+        return Err(ethabi::Error::InvalidData);
+    };
+    let [Token::FixedBytes(hash), Token::Bytes(env_commitment), Token::Bytes(kzg_proof), Token::Uint(chain_id)] =
+        &fields[..]
+    else {
+        return Err(ethabi::Error::InvalidData);
+    };
+    if chain_id.bits() > 64 {
+        return Err(ethabi::Error::InvalidData);
+    }
+
+    Ok(DecodedJournal {
+        hash: hex::encode(hash),
+        env_commitment: hex::encode(env_commitment),
+        kzg_proof: hex::encode(kzg_proof),
+        chain_id: chain_id.as_u64(),
+    })
+}
@@ -0,0 +1,489 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Params of the `generate_proof` method.
+/// The methods that only receive a blob ID, like `get_proof`, use it without the optional fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateProofParams {
+    pub blob_id: String,
+    /// Hex encoded ABI `EigenDACert` of the blob. If given, the proof is generated
+    /// for this cert instead of requesting it from the EigenDA disperser.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eigenda_cert: Option<String>,
+    /// Hex encoded payload of the blob. If given (along with `eigenda_cert`), it is checked
+    /// against the cert blob commitment and proven instead of retrieving it from the EigenDA relays.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<String>,
+    /// Kind of receipt to generate, `groth16` if not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipt_kind: Option<ReceiptKind>,
+    /// Block the cert validity is proven at, the service `anchor_policy` if not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor_policy: Option<AnchorPolicy>,
+}
+
+/// Kind of risc0 receipt a proof request is proven into.
+/// Only `groth16` receipts can be verified onchain, the others are cheaper to generate
+/// and are verified offchain with the risc0 verifier.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReceiptKind {
+    /// One STARK receipt per segment of the execution.
+    Composite,
+    /// A single STARK receipt, with the segments recursively compressed.
+    Succinct,
+    /// The succinct receipt compressed into a Groth16 SNARK, verifiable onchain.
+    #[default]
+    Groth16,
+}
+
+impl ReceiptKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReceiptKind::Composite => "composite",
+            ReceiptKind::Succinct => "succinct",
+            ReceiptKind::Groth16 => "groth16",
+        }
+    }
+}
+
+impl fmt::Display for ReceiptKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Error returned when parsing an unknown [`ReceiptKind`].
+#[derive(Debug)]
+pub struct UnknownReceiptKind(pub String);
+
+impl fmt::Display for UnknownReceiptKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid receipt kind '{}', expected one of: composite, succinct, groth16",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownReceiptKind {}
+
+impl FromStr for ReceiptKind {
+    type Err = UnknownReceiptKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "composite" => Ok(ReceiptKind::Composite),
+            "succinct" => Ok(ReceiptKind::Succinct),
+            "groth16" => Ok(ReceiptKind::Groth16),
+            _ => Err(UnknownReceiptKind(s.to_string())),
+        }
+    }
+}
+
+/// Policy choosing the block the Steel environment of a proof is anchored to, that is the
+/// block at which the guest proves the `checkDACert` call. Encoded as a string: `latest`,
+/// `latest-<N>`, `<N>` or `reference`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum AnchorPolicy {
+    /// The latest block when the guest input is built.
+    #[default]
+    Latest,
+    /// The given number of blocks behind the latest one, so the anchor is unlikely to be reorged.
+    Confirmations(u64),
+    /// The given block number, making the proof deterministic.
+    Block(u64),
+    /// The `reference_block_number` of the cert batch header, at which its signatures are checked.
+    ReferenceBlock,
+}
+
+impl AnchorPolicy {
+    /// Whether the anchor block depends on the latest block when the input is built.
+    /// Inputs anchored with a fixed policy are the same every time they are built.
+    pub fn is_relative(&self) -> bool {
+        matches!(self, AnchorPolicy::Latest | AnchorPolicy::Confirmations(_))
+    }
+}
+
+impl fmt::Display for AnchorPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnchorPolicy::Latest => write!(f, "latest"),
+            AnchorPolicy::Confirmations(confirmations) => write!(f, "latest-{}", confirmations),
+            AnchorPolicy::Block(number) => write!(f, "{}", number),
+            AnchorPolicy::ReferenceBlock => write!(f, "reference"),
+        }
+    }
+}
+
+/// Error returned when parsing an invalid [`AnchorPolicy`].
+#[derive(Debug)]
+pub struct InvalidAnchorPolicy(pub String);
+
+impl fmt::Display for InvalidAnchorPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid anchor policy '{}', expected one of: latest, latest-<confirmations>, <block number>, reference",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for InvalidAnchorPolicy {}
+
+impl FromStr for AnchorPolicy {
+    type Err = InvalidAnchorPolicy;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidAnchorPolicy(s.to_string());
+        match s {
+            "latest" => Ok(AnchorPolicy::Latest),
+            "reference" => Ok(AnchorPolicy::ReferenceBlock),
+            _ => match s.strip_prefix("latest-") {
+                Some(confirmations) => confirmations
+                    .parse()
+                    .map(AnchorPolicy::Confirmations)
+                    .map_err(|_| invalid()),
+                None => s.parse().map(AnchorPolicy::Block).map_err(|_| invalid()),
+            },
+        }
+    }
+}
+
+impl TryFrom<String> for AnchorPolicy {
+    type Error = InvalidAnchorPolicy;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<AnchorPolicy> for String {
+    fn from(anchor_policy: AnchorPolicy) -> Self {
+        anchor_policy.to_string()
+    }
+}
+
+/// State of a proof request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProofState {
+    /// The request is waiting for (or currently going through) proof generation.
+    Queued,
+    /// The proof was generated and stored.
+    Proven,
+    /// The proof generation failed.
+    Failed,
+    /// The request was cancelled before being proven.
+    Cancelled,
+}
+
+impl ProofState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProofState::Queued => "queued",
+            ProofState::Proven => "proven",
+            ProofState::Failed => "failed",
+            ProofState::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl fmt::Display for ProofState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Error returned when parsing an unknown [`ProofState`].
+#[derive(Debug)]
+pub struct UnknownProofState(pub String);
+
+impl fmt::Display for UnknownProofState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid state '{}', expected one of: queued, proven, failed, cancelled",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownProofState {}
+
+impl FromStr for ProofState {
+    type Err = UnknownProofState;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "queued" => Ok(ProofState::Queued),
+            "proven" => Ok(ProofState::Proven),
+            "failed" => Ok(ProofState::Failed),
+            "cancelled" => Ok(ProofState::Cancelled),
+            _ => Err(UnknownProofState(s.to_string())),
+        }
+    }
+}
+
+/// Status of a proof request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofStatus {
+    pub blob_id: String,
+    pub state: ProofState,
+    /// Hex encoded proof, only present once the proof is generated.
+    pub proof: Option<String>,
+    /// Kind of receipt the proof is generated from. Only `groth16` proofs verify onchain.
+    #[serde(default)]
+    pub receipt_kind: ReceiptKind,
+    /// Whether the proof is a fake dev mode proof, which doesn't verify onchain.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fake: bool,
+    /// Policy the block the proof is anchored to is chosen with.
+    #[serde(default)]
+    pub anchor_policy: AnchorPolicy,
+    /// Block the proof is anchored to, only present once the guest input is built.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor_block: Option<u64>,
+    /// Last block the steel environment commitment of the proof can be verified onchain at,
+    /// only present once the guest input is built.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiry_block: Option<u64>,
+    /// Freshness of the proof against the latest block, only present for proven requests
+    /// when the latest block could be retrieved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freshness: Option<ProofFreshness>,
+    /// Whether the proof was marked as consumed, so it's not re-proven when it expires.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub consumed: bool,
+    /// Number of times the proof was invalidated by a reorg of its anchor block and queued again.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub reorgs: u32,
+    /// Statistics of the proof generation, only present once the proof is generated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<ProofStats>,
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+/// Whether a proof can still be verified onchain, given how far its expiry block is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProofFreshness {
+    /// The proof is verifiable for more than the expiry margin.
+    Fresh,
+    /// The proof expires within the expiry margin, and should be used or re-proven soon.
+    Expiring,
+    /// The proof can't be verified onchain anymore.
+    Expired,
+}
+
+impl ProofFreshness {
+    /// Freshness of a proof expiring at `expiry_block` when the latest block is `latest_block`.
+    pub fn at(expiry_block: u64, latest_block: u64, margin_blocks: u64) -> Self {
+        if latest_block > expiry_block {
+            ProofFreshness::Expired
+        } else if latest_block.saturating_add(margin_blocks) >= expiry_block {
+            ProofFreshness::Expiring
+        } else {
+            ProofFreshness::Fresh
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProofFreshness::Fresh => "fresh",
+            ProofFreshness::Expiring => "expiring",
+            ProofFreshness::Expired => "expired",
+        }
+    }
+}
+
+impl fmt::Display for ProofFreshness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Statistics of the generation of a proof.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofStats {
+    /// Size of the proven blob payload, in bytes.
+    pub payload_size: u64,
+    /// Time taken by the prover backend to prove the guest, in seconds.
+    pub proving_secs: f64,
+    /// Statistics of the guest execution, if the prover backend reports them.
+    pub execution: Option<ExecutionStats>,
+}
+
+/// Statistics of a guest execution.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ExecutionStats {
+    /// Cycles of the execution, including the paging and padding cycles.
+    pub total_cycles: u64,
+    /// Cycles run by the guest code itself.
+    pub user_cycles: u64,
+    /// Cycles spent paging memory in and out.
+    pub paging_cycles: u64,
+    /// Number of segments the execution was proven in.
+    pub segments: u64,
+}
+
+/// Params of the `aggregate_proofs` method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregateProofsParams {
+    /// Blob IDs of the proven requests to aggregate, in the order they are committed.
+    pub blob_ids: Vec<String>,
+}
+
+/// Params of the `get_aggregation_status` method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregationIdParams {
+    pub aggregation_id: u64,
+}
+
+/// Status of a request to aggregate many blob proofs into one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregationStatus {
+    pub aggregation_id: u64,
+    /// Blob IDs of the aggregated proofs, in the order they are committed.
+    pub blob_ids: Vec<String>,
+    /// `queued`, `proven` or `failed`.
+    pub state: ProofState,
+    /// Hex encoded aggregated proof, only present once it is generated.
+    pub proof: Option<String>,
+    /// Whether the aggregated proof is a fake dev mode proof, which doesn't verify onchain.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fake: bool,
+}
+
+/// Params of the `list_proofs` method.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListProofsParams {
+    /// Only list the proof requests in this state.
+    #[serde(default)]
+    pub state: Option<ProofState>,
+}
+
+/// State of the proof generation worker, changed through the admin methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkerState {
+    /// The worker is consuming the queue.
+    Running,
+    /// The worker doesn't take new proof requests from the queue.
+    Paused,
+    /// The worker finishes its current proof request, and then pauses.
+    Draining,
+}
+
+impl fmt::Display for WorkerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkerState::Running => write!(f, "running"),
+            WorkerState::Paused => write!(f, "paused"),
+            WorkerState::Draining => write!(f, "draining"),
+        }
+    }
+}
+
+/// Current activity of the proof generation worker, returned by the admin methods.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerActivity {
+    pub state: WorkerState,
+    /// Blob ID of the proof request being proven, if any.
+    pub current_blob_id: Option<String>,
+    /// Seconds the current proof request has been proving for.
+    pub current_elapsed_secs: Option<u64>,
+}
+
+/// Params of the `wait_for_proof` method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaitForProofParams {
+    pub blob_id: String,
+    /// Maximum time to wait for the proof, in seconds. The service caps it
+    /// and uses its own default if not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Result of the `dry_run` method: the guest executed for a blob, without proving it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunResult {
+    pub blob_id: String,
+    /// Whether the guest ran to completion, so the blob can be proven.
+    pub passed: bool,
+    /// Why the dry run failed: the guest panic message, or why its input couldn't be built.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Cycles of the execution, including the paging and padding cycles.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_cycles: Option<u64>,
+    /// Cycles run by the guest code itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_cycles: Option<u64>,
+    /// Number of segments the execution would be proven in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segments: Option<u64>,
+    /// Hex encoded journal the guest would commit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub journal: Option<String>,
+}
+
+/// Configuration the proving service generates its proofs with, returned by `get_service_info`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceInfo {
+    /// Version of the proving service.
+    pub version: String,
+    /// Image ID of the guest program the proofs are generated with, hex encoded.
+    pub image_id: String,
+    /// Image ID of the guest program the aggregated proofs are generated with, hex encoded.
+    #[serde(default)]
+    pub aggregator_image_id: String,
+    /// Version of the risc0 zkVM.
+    pub risc0_version: String,
+    /// Digest of the risc0 Groth16 receipt verifier parameters, hex encoded.
+    pub verifier_parameters: String,
+    /// Selector prepended to the seal, the first 4 bytes of the verifier parameters, hex encoded.
+    pub verifier_selector: String,
+    /// Chain the EigenDA certs are verified against.
+    pub chain: ChainInfo,
+    /// Address of the EigenDA Cert Verifier Router.
+    pub cert_verifier_router_addr: String,
+    /// Payload form of the dispersed blobs (`coeff` or `eval`).
+    pub payload_form: String,
+    /// Anchor policy of the proof requests that don't give one.
+    #[serde(default)]
+    pub anchor_policy: AnchorPolicy,
+    /// What the steel environment of the proofs commits to: the anchor `block` hash,
+    /// or its `beacon` block root (EIP-4788).
+    #[serde(default)]
+    pub env_commitment: String,
+    /// EigenDA blob version.
+    pub blob_version: u16,
+    /// SRS used to compute the KZG proofs.
+    pub srs: SrsInfo,
+    /// Whether dev mode is enabled, in which fake proofs are generated and served.
+    #[serde(default)]
+    pub dev_mode: bool,
+}
+
+/// Chain the proving service verifies EigenDA certs against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainInfo {
+    pub name: String,
+    pub chain_id: u64,
+}
+
+/// SRS loaded by the proving service.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SrsInfo {
+    pub path: String,
+    pub order: u32,
+    pub points_loaded: u32,
+}
@@ -0,0 +1,540 @@
+//! Tests of the client against an in-process JSON RPC server standing in for the proving service.
+
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use client::error::{
+    AGGREGATION_NOT_FOUND_MESSAGE, ALREADY_SUBMITTED_MESSAGE, PROOF_GENERATION_FAILED_MESSAGE,
+    PROOF_NOT_FOUND_ERROR, PROOF_REQUEST_INVALID_STATE_ERROR, PROOF_REQUEST_NOT_FOUND_MESSAGE,
+};
+use client::types::{
+    AggregateProofsParams, AggregationIdParams, GenerateProofParams, ListProofsParams,
+    WaitForProofParams,
+};
+use client::{ClientError, ProofState, ProvingServiceClient, WaitOptions, WorkerState};
+use jsonrpc_core::{Error, ErrorCode, IoHandler, Params, Value};
+use jsonrpc_http_server::ServerBuilder;
+use serde_json::json;
+
+// Blob IDs the stand-in service knows, each in a different state
+const PROVEN: &str = "proven";
+const QUEUED: &str = "queued";
+const FAILED: &str = "failed";
+const CANCELLED: &str = "cancelled";
+// Already submitted, and proven
+const SUBMITTED: &str = "submitted";
+// Proven after two `wait_for_proof` calls
+const PROVING: &str = "proving";
+// Proven with a proof that is not hex
+const GARBLED: &str = "garbled";
+// Proven with a fake dev mode proof, served as in dev mode
+const FAKE: &str = "fake";
+// Proven with a fake dev mode proof, withheld as out of dev mode
+const WITHHELD: &str = "withheld";
+
+const PROOF: &str = "c0ffee";
+const IMAGE_ID: &str = "abcdef";
+
+fn server_error(code: i64, message: &str) -> Error {
+    Error {
+        code: ErrorCode::ServerError(code),
+        message: message.to_string(),
+        data: None,
+    }
+}
+
+fn not_found() -> Error {
+    Error {
+        code: ErrorCode::InternalError,
+        message: PROOF_REQUEST_NOT_FOUND_MESSAGE.to_string(),
+        data: None,
+    }
+}
+
+fn status(blob_id: &str, state: ProofState, proof: Option<&str>) -> Value {
+    json!({
+        "blob_id": blob_id,
+        "state": state,
+        "proof": proof,
+        "receipt_kind": "groth16",
+        "anchor_policy": "latest",
+    })
+}
+
+fn known_status(blob_id: &str) -> Result<Value, Error> {
+    match blob_id {
+        PROVEN | SUBMITTED => Ok(status(blob_id, ProofState::Proven, Some(PROOF))),
+        GARBLED => Ok(status(blob_id, ProofState::Proven, Some("not hex"))),
+        FAKE | WITHHELD => {
+            let proof = (blob_id == FAKE).then_some(PROOF);
+            let mut status = status(blob_id, ProofState::Proven, proof);
+            status["fake"] = Value::Bool(true);
+            Ok(status)
+        }
+        QUEUED => Ok(status(blob_id, ProofState::Queued, None)),
+        FAILED => Ok(status(blob_id, ProofState::Failed, None)),
+        CANCELLED => Ok(status(blob_id, ProofState::Cancelled, None)),
+        _ => Err(not_found()),
+    }
+}
+
+fn worker_activity(state: WorkerState) -> Value {
+    json!({
+        "state": state,
+        "current_blob_id": QUEUED,
+        "current_elapsed_secs": 7,
+    })
+}
+
+fn stand_in_service() -> IoHandler {
+    let mut io = IoHandler::new();
+
+    io.add_sync_method("generate_proof", |params: Params| {
+        let params: GenerateProofParams = params.parse()?;
+        match params.blob_id.as_str() {
+            SUBMITTED => Err(Error::invalid_params(ALREADY_SUBMITTED_MESSAGE)),
+            "" => Err(Error::invalid_params(
+                "Expected a string parameter 'blob_id'",
+            )),
+            "crash" => Err(Error::internal_error()),
+            _ => Ok(Value::String("Proof request received".to_string())),
+        }
+    });
+    io.add_sync_method("dry_run", |params: Params| {
+        let params: GenerateProofParams = params.parse()?;
+        Ok(json!({
+            "blob_id": params.blob_id,
+            "passed": params.payload.is_some(),
+            "total_cycles": 1 << 20,
+        }))
+    });
+    io.add_sync_method("get_proof", |params: Params| {
+        let params: GenerateProofParams = params.parse()?;
+        let status = known_status(&params.blob_id)?;
+        match status["state"].as_str() {
+            Some("proven") => Ok(status["proof"].clone()),
+            Some("failed") => Err(Error::invalid_params(PROOF_GENERATION_FAILED_MESSAGE)),
+            _ => Err(server_error(
+                PROOF_NOT_FOUND_ERROR,
+                "Proof not found (still queued)",
+            )),
+        }
+    });
+    io.add_sync_method("get_proof_status", |params: Params| {
+        let params: GenerateProofParams = params.parse()?;
+        known_status(&params.blob_id)
+    });
+    io.add_sync_method("list_proofs", |params: Params| {
+        let params: ListProofsParams = params.parse()?;
+        let statuses = [PROVEN, QUEUED, FAILED, CANCELLED]
+            .into_iter()
+            .filter_map(|blob_id| known_status(blob_id).ok())
+            .filter(|status| match params.state {
+                Some(state) => status["state"] == json!(state),
+                None => true,
+            })
+            .collect();
+        Ok(Value::Array(statuses))
+    });
+    io.add_sync_method("cancel_proof", |params: Params| {
+        let params: GenerateProofParams = params.parse()?;
+        match params.blob_id.as_str() {
+            QUEUED => Ok(status(QUEUED, ProofState::Cancelled, None)),
+            _ => Err(server_error(
+                PROOF_REQUEST_INVALID_STATE_ERROR,
+                "Only queued proof requests can be cancelled",
+            )),
+        }
+    });
+    io.add_sync_method("retry_proof", |params: Params| {
+        let params: GenerateProofParams = params.parse()?;
+        known_status(&params.blob_id)?;
+        Ok(status(&params.blob_id, ProofState::Queued, None))
+    });
+    io.add_sync_method("recompress_proof", |params: Params| {
+        let params: GenerateProofParams = params.parse()?;
+        known_status(&params.blob_id)?;
+        Ok(status(&params.blob_id, ProofState::Queued, None))
+    });
+    io.add_sync_method("mark_proof_consumed", |params: Params| {
+        let params: GenerateProofParams = params.parse()?;
+        let mut status = known_status(&params.blob_id)?;
+        status["consumed"] = Value::Bool(true);
+        Ok(status)
+    });
+    io.add_sync_method("export_guest_input", |params: Params| {
+        let params: GenerateProofParams = params.parse()?;
+        known_status(&params.blob_id)?;
+        Ok(json!({ "version": 3, "blob_id": params.blob_id }))
+    });
+    io.add_sync_method("aggregate_proofs", |params: Params| {
+        let params: AggregateProofsParams = params.parse()?;
+        Ok(json!({
+            "aggregation_id": 1,
+            "blob_ids": params.blob_ids,
+            "state": "queued",
+            "proof": null,
+        }))
+    });
+    io.add_sync_method("get_aggregation_status", |params: Params| {
+        let params: AggregationIdParams = params.parse()?;
+        match params.aggregation_id {
+            1 => Ok(json!({
+                "aggregation_id": 1,
+                "blob_ids": [PROVEN],
+                "state": "proven",
+                "proof": PROOF,
+            })),
+            _ => Err(Error {
+                code: ErrorCode::InternalError,
+                message: AGGREGATION_NOT_FOUND_MESSAGE.to_string(),
+                data: None,
+            }),
+        }
+    });
+    io.add_sync_method("get_service_info", |_: Params| {
+        Ok(json!({
+            "version": "0.1.0",
+            "image_id": IMAGE_ID,
+            "risc0_version": "2.1.0",
+            "verifier_parameters": "00",
+            "verifier_selector": "00",
+            "chain": { "name": "holesky", "chain_id": 17000 },
+            "cert_verifier_router_addr": "0x0000000000000000000000000000000000000000",
+            "payload_form": "coeff",
+            "blob_version": 0,
+            "srs": { "path": "resources/g1.point", "order": 1, "points_loaded": 1 },
+        }))
+    });
+    io.add_sync_method("admin_pause", |_: Params| {
+        Ok(worker_activity(WorkerState::Paused))
+    });
+    io.add_sync_method("admin_resume", |_: Params| {
+        Ok(worker_activity(WorkerState::Running))
+    });
+    io.add_sync_method("admin_drain", |_: Params| {
+        Ok(worker_activity(WorkerState::Draining))
+    });
+    io.add_sync_method("admin_worker_activity", |_: Params| {
+        Ok(worker_activity(WorkerState::Running))
+    });
+
+    let proving_waits = Arc::new(AtomicU64::new(0));
+    io.add_sync_method("wait_for_proof", move |params: Params| {
+        let params: WaitForProofParams = params.parse()?;
+        if params.timeout_secs.is_none() {
+            return Err(Error::invalid_params("Expected a 'timeout_secs'"));
+        }
+        match params.blob_id.as_str() {
+            PROVING if proving_waits.fetch_add(1, Ordering::SeqCst) >= 2 => {
+                Ok(status(PROVING, ProofState::Proven, Some(PROOF)))
+            }
+            PROVING => Ok(status(PROVING, ProofState::Queued, None)),
+            blob_id => known_status(blob_id),
+        }
+    });
+
+    io
+}
+
+/// Starts the stand-in service on an ephemeral port and returns a client pointing to it.
+fn start() -> ProvingServiceClient {
+    let server = ServerBuilder::new(stand_in_service())
+        .start_http(&SocketAddr::from(([127, 0, 0, 1], 0)))
+        .expect("Failed to start the stand-in service");
+    let url = format!("http://{}", server.address());
+    // The server runs its own runtime, which can't be dropped from the tests runtime,
+    // so it's kept alive in its own thread until the tests end.
+    std::thread::spawn(move || server.wait());
+    ProvingServiceClient::new(url)
+}
+
+fn wait_options(timeout: Duration) -> WaitOptions {
+    WaitOptions {
+        poll_interval: Duration::from_secs(1),
+        timeout: Some(timeout),
+        allow_fake: false,
+    }
+}
+
+#[tokio::test]
+async fn generate_proof() {
+    let client = start();
+
+    client.generate_proof(QUEUED).await.unwrap();
+    client
+        .generate_proof_with_cert(QUEUED, &[1, 2, 3])
+        .await
+        .unwrap();
+    client
+        .generate_proof_with_payload(QUEUED, &[1, 2, 3], &[4, 5, 6])
+        .await
+        .unwrap();
+    client
+        .generate_proof_with_params(GenerateProofParams {
+            blob_id: QUEUED.to_string(),
+            eigenda_cert: None,
+            payload: None,
+            receipt_kind: None,
+            anchor_policy: None,
+        })
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        client.generate_proof(SUBMITTED).await,
+        Err(ClientError::AlreadySubmitted)
+    ));
+    assert!(matches!(
+        client.generate_proof("").await,
+        Err(ClientError::InvalidParams(_))
+    ));
+    assert!(matches!(
+        client.generate_proof("crash").await,
+        Err(ClientError::Internal(_))
+    ));
+}
+
+#[tokio::test]
+async fn dry_run() {
+    let client = start();
+
+    let result = client
+        .dry_run(QUEUED, Some(&[1]), Some(&[2]))
+        .await
+        .unwrap();
+    assert_eq!(result.blob_id, QUEUED);
+    assert!(result.passed);
+    assert_eq!(result.total_cycles, Some(1 << 20));
+
+    let result = client.dry_run(QUEUED, None, None).await.unwrap();
+    assert!(!result.passed);
+}
+
+#[tokio::test]
+async fn get_proof() {
+    let client = start();
+
+    assert_eq!(
+        client.get_proof(PROVEN).await.unwrap(),
+        vec![0xc0, 0xff, 0xee]
+    );
+    assert!(matches!(
+        client.get_proof(QUEUED).await,
+        Err(ClientError::ProofQueued)
+    ));
+    assert!(matches!(
+        client.get_proof(FAILED).await,
+        Err(ClientError::ProofGenerationFailed)
+    ));
+    assert!(matches!(
+        client.get_proof("unknown").await,
+        Err(ClientError::ProofRequestNotFound)
+    ));
+    assert!(matches!(
+        client.get_proof(GARBLED).await,
+        Err(ClientError::InvalidResponse(_))
+    ));
+
+    assert_eq!(
+        client.try_get_proof(PROVEN).await.unwrap(),
+        Some(vec![0xc0, 0xff, 0xee])
+    );
+    assert_eq!(client.try_get_proof(QUEUED).await.unwrap(), None);
+    assert!(matches!(
+        client.try_get_proof(FAILED).await,
+        Err(ClientError::ProofGenerationFailed)
+    ));
+}
+
+#[tokio::test]
+async fn proof_statuses() {
+    let client = start();
+
+    let status = client.get_proof_status(PROVEN).await.unwrap();
+    assert_eq!(status.state, ProofState::Proven);
+    assert_eq!(status.proof.as_deref(), Some(PROOF));
+    assert!(matches!(
+        client.get_proof_status("unknown").await,
+        Err(ClientError::ProofRequestNotFound)
+    ));
+
+    assert_eq!(client.list_proofs(None).await.unwrap().len(), 4);
+    let failed = client.list_proofs(Some(ProofState::Failed)).await.unwrap();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].blob_id, FAILED);
+}
+
+#[tokio::test]
+async fn manage_proof_requests() {
+    let client = start();
+
+    let status = client.cancel_proof(QUEUED).await.unwrap();
+    assert_eq!(status.state, ProofState::Cancelled);
+    assert!(matches!(
+        client.cancel_proof(PROVEN).await,
+        Err(ClientError::InvalidState(_))
+    ));
+
+    let status = client.retry_proof(FAILED).await.unwrap();
+    assert_eq!(status.state, ProofState::Queued);
+    let status = client.recompress_proof(PROVEN).await.unwrap();
+    assert_eq!(status.state, ProofState::Queued);
+    assert!(matches!(
+        client.retry_proof("unknown").await,
+        Err(ClientError::ProofRequestNotFound)
+    ));
+
+    let status = client.mark_proof_consumed(PROVEN).await.unwrap();
+    assert!(status.consumed);
+
+    let bundle = client.export_guest_input(PROVEN).await.unwrap();
+    assert_eq!(bundle["blob_id"], PROVEN);
+}
+
+#[tokio::test]
+async fn aggregation() {
+    let client = start();
+
+    let status = client
+        .aggregate_proofs(&[PROVEN.to_string()])
+        .await
+        .unwrap();
+    assert_eq!(status.aggregation_id, 1);
+    assert_eq!(status.blob_ids, vec![PROVEN.to_string()]);
+    assert_eq!(status.state, ProofState::Queued);
+
+    let status = client.get_aggregation_status(1).await.unwrap();
+    assert_eq!(status.state, ProofState::Proven);
+    assert_eq!(status.proof.as_deref(), Some(PROOF));
+    assert!(matches!(
+        client.get_aggregation_status(2).await,
+        Err(ClientError::AggregationNotFound)
+    ));
+}
+
+#[tokio::test]
+async fn service_info() {
+    let client = start();
+
+    let info = client.get_service_info().await.unwrap();
+    assert_eq!(info.chain.chain_id, 17000);
+    assert!(!info.dev_mode);
+
+    client.ensure_image_id("0xABCDEF").await.unwrap();
+    match client.ensure_image_id("012345").await {
+        Err(ClientError::ImageIdMismatch { expected, actual }) => {
+            assert_eq!(expected, "012345");
+            assert_eq!(actual, IMAGE_ID);
+        }
+        other => panic!("Expected an image ID mismatch, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn admin() {
+    let client = start();
+
+    assert_eq!(
+        client.pause_worker().await.unwrap().state,
+        WorkerState::Paused
+    );
+    assert_eq!(
+        client.drain_worker().await.unwrap().state,
+        WorkerState::Draining
+    );
+    assert_eq!(
+        client.resume_worker().await.unwrap().state,
+        WorkerState::Running
+    );
+    let activity = client.get_worker_activity().await.unwrap();
+    assert_eq!(activity.current_blob_id.as_deref(), Some(QUEUED));
+    assert_eq!(activity.current_elapsed_secs, Some(7));
+}
+
+#[tokio::test]
+async fn wait_for_proof() {
+    let client = start();
+    let options = wait_options(Duration::from_secs(10));
+
+    let status = client
+        .wait_for_proof_status(QUEUED, Duration::from_millis(10))
+        .await
+        .unwrap();
+    assert_eq!(status.state, ProofState::Queued);
+
+    assert_eq!(
+        client.wait_for_proof(PROVING, &options).await.unwrap(),
+        vec![0xc0, 0xff, 0xee]
+    );
+    assert!(matches!(
+        client.wait_for_proof(FAILED, &options).await,
+        Err(ClientError::ProofGenerationFailed)
+    ));
+    assert!(matches!(
+        client.wait_for_proof(CANCELLED, &options).await,
+        Err(ClientError::InvalidState(_))
+    ));
+    assert!(matches!(
+        client.wait_for_proof(FAKE, &options).await,
+        Err(ClientError::InvalidState(_))
+    ));
+    let allow_fake = WaitOptions {
+        allow_fake: true,
+        ..options.clone()
+    };
+    assert_eq!(
+        client.wait_for_proof(FAKE, &allow_fake).await.unwrap(),
+        vec![0xc0, 0xff, 0xee]
+    );
+    assert!(matches!(
+        client.wait_for_proof(WITHHELD, &allow_fake).await,
+        Err(ClientError::InvalidState(_))
+    ));
+    assert!(matches!(
+        client.wait_for_proof("unknown", &options).await,
+        Err(ClientError::ProofRequestNotFound)
+    ));
+    assert!(matches!(
+        client
+            .wait_for_proof(QUEUED, &wait_options(Duration::from_millis(200)))
+            .await,
+        Err(ClientError::Timeout)
+    ));
+
+    assert_eq!(
+        client.generate_and_wait(SUBMITTED, &options).await.unwrap(),
+        vec![0xc0, 0xff, 0xee]
+    );
+    assert!(matches!(
+        client.generate_and_wait("crash", &options).await,
+        Err(ClientError::Internal(_))
+    ));
+}
+
+#[tokio::test]
+async fn rpc_and_transport_errors() {
+    let client = start();
+
+    match client.call::<_, Value>("unknown_method", ()).await {
+        Err(ClientError::Rpc { code, .. }) => assert_eq!(code, -32601),
+        other => panic!("Expected an unknown method error, got {:?}", other),
+    }
+    assert!(matches!(
+        ClientError::from_rpc_error(PROOF_REQUEST_INVALID_STATE_ERROR, String::new()),
+        ClientError::InvalidState(_)
+    ));
+
+    // Nothing listens on the discard port
+    let client = ProvingServiceClient::new("http://127.0.0.1:9");
+    assert!(matches!(
+        client.get_service_info().await,
+        Err(ClientError::Transport(_))
+    ));
+}
@@ -0,0 +1,70 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Chain ID of local devnets (anvil, hardhat) if not given.
+pub const DEVNET_CHAIN_ID: u64 = 31337;
+
+/// Chain the EigenDA certs are verified against. It selects the risc0 steel chain spec
+/// the guest executes the `checkDACert` call with, and its chain ID is committed in the journal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Chain {
+    Mainnet,
+    Sepolia,
+    Holesky,
+    /// Local devnet with the given chain ID, running the latest hardfork since genesis.
+    Devnet(u64),
+}
+
+impl Chain {
+    pub fn chain_id(&self) -> u64 {
+        match self {
+            Chain::Mainnet => 1,
+            Chain::Sepolia => 11155111,
+            Chain::Holesky => 17000,
+            Chain::Devnet(chain_id) => *chain_id,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Chain::Mainnet => "mainnet",
+            Chain::Sepolia => "sepolia",
+            Chain::Holesky => "holesky",
+            Chain::Devnet(_) => "devnet",
+        }
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Chain::Devnet(chain_id) => write!(f, "devnet:{}", chain_id),
+            _ => write!(f, "{}", self.name()),
+        }
+    }
+}
+
+impl FromStr for Chain {
+    type Err = String;
+
+    /// Parses `mainnet`, `sepolia`, `holesky`, `devnet` or `devnet:<chain_id>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mainnet" => Ok(Chain::Mainnet),
+            "sepolia" => Ok(Chain::Sepolia),
+            "holesky" => Ok(Chain::Holesky),
+            "devnet" => Ok(Chain::Devnet(DEVNET_CHAIN_ID)),
+            _ => s
+                .strip_prefix("devnet:")
+                .and_then(|chain_id| chain_id.parse().ok())
+                .map(Chain::Devnet)
+                .ok_or_else(|| {
+                    format!(
+                        "Invalid chain '{}', expected one of: mainnet, sepolia, holesky, devnet, devnet:<chain_id>",
+                        s
+                    )
+                }),
+        }
+    }
+}
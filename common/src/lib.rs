@@ -1,3 +1,4 @@
+pub mod chain;
 pub mod output;
 pub mod polynomial_form;
 pub mod serializable_g1;
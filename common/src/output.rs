@@ -1,9 +1,11 @@
-use ethabi::Token;
+use ethabi::{ParamType, Token};
 
 pub struct Output {
     pub hash: Vec<u8>,
     pub env_commitment: Vec<u8>,
     pub proof: Vec<u8>,
+    /// ID of the chain the EigenDA cert was verified against.
+    pub chain_id: u64,
 }
 
 
@@ -13,8 +15,75 @@ impl Output {
             vec![
                 Token::FixedBytes(self.hash),
                 Token::Bytes(self.env_commitment),
-                Token::Bytes(self.proof)
+                Token::Bytes(self.proof),
+                Token::Uint(self.chain_id.into())
             ]
         )])
     }
+
+    /// Decodes the output committed by the guest, the inverse of `abi_encode`.
+    pub fn abi_decode(data: &[u8]) -> Result<Self, ethabi::Error> {
+        let param = ParamType::Tuple(vec![
+            ParamType::FixedBytes(32),
+            ParamType::Bytes,
+            ParamType::Bytes,
+            ParamType::Uint(64),
+        ]);
+        let tokens = ethabi::decode(&[param], data)?;
+        let fields = tokens
+            .into_iter()
+            .next()
+            .and_then(Token::into_tuple)
+            .ok_or(ethabi::Error::InvalidData)?;
+        match <[Token; 4]>::try_from(fields) {
+            Ok([hash, env_commitment, proof, chain_id]) => Ok(Self {
+                hash: hash.into_fixed_bytes().ok_or(ethabi::Error::InvalidData)?,
+                env_commitment: env_commitment.into_bytes().ok_or(ethabi::Error::InvalidData)?,
+                proof: proof.into_bytes().ok_or(ethabi::Error::InvalidData)?,
+                chain_id: chain_id
+                    .into_uint()
+                    .filter(|chain_id| chain_id.bits() <= 64)
+                    .ok_or(ethabi::Error::InvalidData)?
+                    .as_u64(),
+            }),
+            Err(_) => Err(ethabi::Error::InvalidData),
+        }
+    }
+}
+
+/// A blob verified by the aggregation guest.
+pub struct AggregatedBlob {
+    /// Keccak256 hash of the blob payload, as committed by the blob guest.
+    pub hash: Vec<u8>,
+    /// Commitment to the risc0 steel environment the blob cert was verified in.
+    pub env_commitment: Vec<u8>,
+    /// ID of the chain the blob cert was verified against.
+    pub chain_id: u64,
+}
+
+/// Public outputs of the aggregation guest: the image ID of the blob guest whose
+/// receipts were verified, and the blobs they proved, in order.
+pub struct AggregatedOutput {
+    pub image_id: Vec<u8>,
+    pub blobs: Vec<AggregatedBlob>,
+}
+
+impl AggregatedOutput {
+    pub fn abi_encode(self) -> Vec<u8> {
+        let blobs = self
+            .blobs
+            .into_iter()
+            .map(|blob| {
+                Token::Tuple(vec![
+                    Token::FixedBytes(blob.hash),
+                    Token::Bytes(blob.env_commitment),
+                    Token::Uint(blob.chain_id.into()),
+                ])
+            })
+            .collect();
+        ethabi::encode(&[Token::Tuple(vec![
+            Token::FixedBytes(self.image_id),
+            Token::Array(blobs),
+        ])])
+    }
 }
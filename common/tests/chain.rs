@@ -0,0 +1,41 @@
+//! Tests of the parsing and display of the chains, as given on the command line.
+
+use common::chain::{Chain, DEVNET_CHAIN_ID};
+
+#[test]
+fn parses_what_it_displays() {
+    for chain in [
+        Chain::Mainnet,
+        Chain::Sepolia,
+        Chain::Holesky,
+        Chain::Devnet(DEVNET_CHAIN_ID),
+        Chain::Devnet(1337),
+    ] {
+        assert_eq!(chain.to_string().parse::<Chain>(), Ok(chain));
+    }
+}
+
+#[test]
+fn parses_the_default_devnet() {
+    assert_eq!(
+        "devnet".parse::<Chain>(),
+        Ok(Chain::Devnet(DEVNET_CHAIN_ID))
+    );
+    assert_eq!(Chain::Devnet(1337).to_string(), "devnet:1337");
+    assert_eq!(Chain::Sepolia.chain_id(), 11155111);
+}
+
+#[test]
+fn rejects_unknown_chains() {
+    for chain in [
+        "",
+        "goerli",
+        "Mainnet",
+        "devnet:",
+        "devnet:0x7a69",
+        "devnet:-1",
+    ] {
+        let err = chain.parse::<Chain>().unwrap_err();
+        assert!(err.contains(&format!("'{}'", chain)), "{}", err);
+    }
+}
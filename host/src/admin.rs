@@ -0,0 +1,144 @@
+use std::{
+    sync::{Arc, Mutex as StdMutex},
+    time::Instant,
+};
+
+use anyhow::Result;
+use sqlx::{Pool, Postgres};
+use tokio::sync::{watch, Mutex};
+
+use crate::db::{retrieve_worker_paused, store_worker_paused};
+use crate::service::ServiceError;
+
+pub use client::types::{WorkerActivity, WorkerState};
+
+/// Proof request being proven by the worker.
+struct CurrentJob {
+    blob_id: String,
+    started: Instant,
+}
+
+/// Lets the admin methods pause, resume and drain the proof generation worker.
+/// The paused state is persisted in the database, so restarts honour it.
+pub struct WorkerControl {
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    state: watch::Sender<WorkerState>,
+    current_job: StdMutex<Option<CurrentJob>>,
+}
+
+impl WorkerControl {
+    /// Creates the worker control, starting paused if the worker was paused before a restart.
+    pub async fn load(db_pool: Arc<Mutex<Pool<Postgres>>>) -> Result<Self> {
+        let state = if retrieve_worker_paused(db_pool.clone()).await? {
+            tracing::warn!("Proof generation worker is paused, use admin_resume to resume it");
+            WorkerState::Paused
+        } else {
+            WorkerState::Running
+        };
+        Ok(Self {
+            db_pool,
+            state: watch::Sender::new(state),
+            current_job: StdMutex::new(None),
+        })
+    }
+
+    pub fn state(&self) -> WorkerState {
+        *self.state.borrow()
+    }
+
+    /// Waits until the worker state is the given one.
+    pub async fn wait_for_state(&self, state: WorkerState) {
+        let mut receiver = self.state.subscribe();
+        // Never fails, the sender lives as long as `self`
+        let _ = receiver.wait_for(|current| *current == state).await;
+    }
+
+    /// Stops taking new proof requests. The request being proven is still finished and stored,
+    /// unlike [`WorkerControl::drain`] the state is `Paused` right away.
+    pub async fn pause(&self) -> Result<WorkerActivity, ServiceError> {
+        tracing::info!("Pausing proof generation worker");
+        self.persist_paused(true).await?;
+        self.state.send_replace(WorkerState::Paused);
+        Ok(self.activity())
+    }
+
+    /// Resumes taking proof requests from the queue.
+    pub async fn resume(&self) -> Result<WorkerActivity, ServiceError> {
+        tracing::info!("Resuming proof generation worker");
+        self.persist_paused(false).await?;
+        self.state.send_replace(WorkerState::Running);
+        Ok(self.activity())
+    }
+
+    /// Lets the worker finish the proof request being proven, and then pauses it.
+    pub async fn drain(&self) -> Result<WorkerActivity, ServiceError> {
+        tracing::info!("Draining proof generation worker");
+        // Persisted as paused right away, a restart while draining comes back paused
+        self.persist_paused(true).await?;
+        // Hold the current job while updating the state, so it can't finish in between
+        let current_job = self.lock_current_job();
+        let busy = current_job.is_some();
+        self.state.send_if_modified(|state| {
+            let next = match (*state, busy) {
+                (WorkerState::Running, true) => WorkerState::Draining,
+                (WorkerState::Running, false) => WorkerState::Paused,
+                (current, _) => current,
+            };
+            let modified = next != *state;
+            *state = next;
+            modified
+        });
+        drop(current_job);
+        Ok(self.activity())
+    }
+
+    /// Returns the worker state and the proof request it is proving, if any.
+    pub fn activity(&self) -> WorkerActivity {
+        let current_job = self.lock_current_job();
+        WorkerActivity {
+            state: self.state(),
+            current_blob_id: current_job.as_ref().map(|job| job.blob_id.clone()),
+            current_elapsed_secs: current_job
+                .as_ref()
+                .map(|job| job.started.elapsed().as_secs()),
+        }
+    }
+
+    /// Records that the worker started proving a request.
+    pub(crate) fn job_started(&self, blob_id: &str) {
+        *self.lock_current_job() = Some(CurrentJob {
+            blob_id: blob_id.to_string(),
+            started: Instant::now(),
+        });
+    }
+
+    /// Records that the worker is done with its proof request, pausing it if it was draining.
+    pub(crate) fn job_finished(&self) {
+        let mut current_job = self.lock_current_job();
+        *current_job = None;
+        self.state.send_if_modified(|state| {
+            if *state == WorkerState::Draining {
+                tracing::info!("Proof generation worker drained");
+                *state = WorkerState::Paused;
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    fn lock_current_job(&self) -> std::sync::MutexGuard<'_, Option<CurrentJob>> {
+        self.current_job
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    async fn persist_paused(&self, paused: bool) -> Result<(), ServiceError> {
+        store_worker_paused(self.db_pool.clone(), paused)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed persisting worker paused state: {}", e);
+                ServiceError::Internal
+            })
+    }
+}
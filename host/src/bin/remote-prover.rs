@@ -0,0 +1,146 @@
+//! Stand-in of a remote prover: serves the API used by the `remote` prover backend
+//! (`POST /prove?receipt_kind={kind}` with the guest stdin as body, answered with the JSON encoded
+//! composite or succinct receipt and execution stats, `POST /aggregate` with the JSON encoded aggregation guest stdin and blob
+//! receipts as body, answered in the same way, and `POST /compress` with a JSON encoded succinct
+//! receipt as body, answered with the JSON encoded Groth16 receipt) on top of a local or mock backend,
+//! to run the proving service against it.
+
+use std::{io::Read, sync::Arc};
+
+use anyhow::Result;
+use clap::{builder::BoolishValueParser, Parser};
+use client::types::{ReceiptKind, UnknownReceiptKind};
+use host::prover::{build_prover_backend, AggregateRequest, ProverBackend, ProverBackendKind};
+use risc0_zkvm::Receipt;
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server};
+use tokio::runtime::Handle;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser, Debug)]
+#[command(about = "Stand-in remote prover for the EigenDA proving service", long_about = None)]
+struct Args {
+    /// Address where the remote prover should run
+    #[arg(
+        long,
+        env = "REMOTE_PROVER_LISTEN_URL",
+        default_value = "127.0.0.1:3100"
+    )]
+    listen_url: String,
+    /// Backend the receipts are generated with
+    #[arg(long, value_enum, env = "PROVER_BACKEND", default_value_t = ProverBackendKind::local())]
+    prover_backend: ProverBackendKind,
+    /// Enables dev mode, allowing the dev and mock prover backends, which generate fake receipts
+    #[arg(long, env = "PROVING_SERVICE_DEV_MODE", value_parser = BoolishValueParser::new())]
+    dev_mode: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+    let args = Args::parse();
+    if args.prover_backend == ProverBackendKind::Remote {
+        anyhow::bail!("The remote prover can't use the remote prover backend itself");
+    }
+    let prover = build_prover_backend(args.prover_backend, None, args.dev_mode)?;
+
+    let server = Server::http(&args.listen_url)
+        .map_err(|_| anyhow::anyhow!("Failed to start remote prover server"))?;
+    tracing::info!(
+        "Running {} remote prover on {}",
+        args.prover_backend,
+        args.listen_url
+    );
+
+    let handle = Handle::current();
+    tokio::task::spawn_blocking(move || {
+        for request in server.incoming_requests() {
+            let prover = prover.clone();
+            let runtime = handle.clone();
+            handle.spawn_blocking(move || handle_request(request, prover, runtime));
+        }
+    })
+    .await?;
+    Ok(())
+}
+
+fn handle_request(mut request: Request, prover: Arc<dyn ProverBackend>, handle: Handle) {
+    if *request.method() != Method::Post {
+        let _ = request.respond(json_response(404, &json!({ "error": "Not Found" })));
+        return;
+    }
+
+    let mut body = Vec::new();
+    if request.as_reader().read_to_end(&mut body).is_err() {
+        let _ = request.respond(json_response(
+            400,
+            &json!({ "error": "Failed reading request body" }),
+        ));
+        return;
+    }
+
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((url.as_str(), ""));
+
+    let response = match path {
+        "/prove" => match parse_receipt_kind(query) {
+            Ok(receipt_kind) => match handle.block_on(prover.prove(body, receipt_kind)) {
+                Ok(output) => json_response(200, &output),
+                Err(e) => {
+                    tracing::error!("Failed proving: {}", e);
+                    json_response(500, &json!({ "error": e.to_string() }))
+                }
+            },
+            Err(e) => json_response(400, &json!({ "error": e.to_string() })),
+        },
+        "/aggregate" => match serde_json::from_slice::<AggregateRequest>(&body) {
+            Ok(AggregateRequest { stdin, receipts }) => {
+                match handle.block_on(prover.aggregate(stdin, receipts)) {
+                    Ok(output) => json_response(200, &output),
+                    Err(e) => {
+                        tracing::error!("Failed aggregating: {}", e);
+                        json_response(500, &json!({ "error": e.to_string() }))
+                    }
+                }
+            }
+            Err(e) => json_response(
+                400,
+                &json!({ "error": format!("Invalid aggregate request: {}", e) }),
+            ),
+        },
+        "/compress" => match serde_json::from_slice::<Receipt>(&body) {
+            Ok(receipt) => match handle.block_on(prover.compress(receipt)) {
+                Ok(receipt) => json_response(200, &receipt),
+                Err(e) => {
+                    tracing::error!("Failed compressing: {}", e);
+                    json_response(500, &json!({ "error": e.to_string() }))
+                }
+            },
+            Err(e) => json_response(400, &json!({ "error": format!("Invalid receipt: {}", e) })),
+        },
+        _ => json_response(404, &json!({ "error": "Not Found" })),
+    };
+    let _ = request.respond(response);
+}
+
+/// Parses the `receipt_kind` query parameter, `groth16` (proven succinct) if not given.
+fn parse_receipt_kind(query: &str) -> Result<ReceiptKind, UnknownReceiptKind> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == "receipt_kind")
+        .map_or(Ok(ReceiptKind::default()), |(_, value)| value.parse())
+}
+
+fn json_response<T: serde::Serialize>(
+    status_code: u16,
+    body: &T,
+) -> Response<std::io::Cursor<Vec<u8>>> {
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("static header is valid");
+    Response::from_data(serde_json::to_vec(body).unwrap_or_default())
+        .with_status_code(status_code)
+        .with_header(content_type)
+}
@@ -0,0 +1,103 @@
+//! Replays a guest input bundle exported with `export_guest_input` (or the `export-input` CLI
+//! command) offline: executes the guest, or proves it with a prover backend, without fetching
+//! anything from the network. Used to reproduce proof failures outside of the service.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{builder::BoolishValueParser, Parser};
+use client::types::ReceiptKind;
+use common::output::Output;
+use host::guest_caller::{execute_guest, run_guest, GuestInput, GuestInputBundle};
+use host::prover::{build_prover_backend, ProveOutput, ProverBackendKind};
+use host::worker::encode_proof;
+use methods::{GUEST_ELF, GUEST_ID};
+use risc0_zkvm::{compute_image_id, InnerReceipt};
+use tracing_subscriber::EnvFilter;
+use url::Url;
+
+#[derive(Parser, Debug)]
+#[command(about = "Replays an exported guest input bundle offline", long_about = None)]
+struct Args {
+    /// File with the JSON encoded guest input bundle
+    bundle: PathBuf,
+    /// Prove the guest instead of only executing it
+    #[arg(long)]
+    prove: bool,
+    /// Kind of receipt the guest is proven into
+    #[arg(long, default_value_t = ReceiptKind::Groth16)]
+    receipt_kind: ReceiptKind,
+    /// Backend the guest is proven with
+    #[arg(long, value_enum, env = "PROVER_BACKEND", default_value_t = ProverBackendKind::local())]
+    prover_backend: ProverBackendKind,
+    /// URL of the remote prover, required by the remote prover backend
+    #[arg(long, env = "REMOTE_PROVER_URL")]
+    remote_prover_url: Option<Url>,
+    /// Enables dev mode, allowing the dev and mock prover backends, which generate fake receipts
+    #[arg(long, env = "PROVING_SERVICE_DEV_MODE", value_parser = BoolishValueParser::new())]
+    dev_mode: bool,
+    /// File the JSON encoded receipt is written to, when proving
+    #[arg(long)]
+    receipt_output: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+    let args = Args::parse();
+
+    let bundle: GuestInputBundle = serde_json::from_slice(&std::fs::read(&args.bundle)?)?;
+    println!("chain:          {}", bundle.chain);
+    let input = GuestInput::from_bundle(bundle)?;
+    println!("anchor_block:   {}", input.anchor_block_number);
+    println!("anchor_hash:    {:#x}", input.anchor_block_hash);
+    println!("payload_size:   {}", input.data.len());
+
+    if !args.prove {
+        let stdin = input.to_stdin()?;
+        let session = tokio::task::spawn_blocking(move || execute_guest(&stdin)).await??;
+        println!("total_cycles:   {}", session.total_cycles);
+        println!("user_cycles:    {}", session.user_cycles);
+        println!("segments:       {}", session.segments.len());
+        if let Some(journal) = session.journal {
+            print_output(&journal.bytes)?;
+        }
+        return Ok(());
+    }
+
+    let prover = build_prover_backend(args.prover_backend, args.remote_prover_url, args.dev_mode)?;
+    let ProveOutput { mut receipt, stats } =
+        run_guest(prover.as_ref(), input, args.receipt_kind).await?;
+    if let Some(stats) = stats {
+        println!("total_cycles:   {}", stats.total_cycles);
+        println!("user_cycles:    {}", stats.user_cycles);
+        println!("segments:       {}", stats.segments);
+    }
+    if args.receipt_kind == ReceiptKind::Groth16 && !matches!(receipt.inner, InnerReceipt::Fake(_))
+    {
+        receipt = prover.compress(receipt).await?;
+    }
+    if !matches!(receipt.inner, InnerReceipt::Fake(_)) {
+        receipt.verify(GUEST_ID)?;
+        println!("receipt:        verified");
+    }
+    print_output(&receipt.journal.bytes)?;
+    println!(
+        "proof:          {}",
+        hex::encode(encode_proof(&receipt, compute_image_id(GUEST_ELF)?)?)
+    );
+    if let Some(receipt_output) = args.receipt_output {
+        std::fs::write(receipt_output, serde_json::to_string(&receipt)?)?;
+    }
+    Ok(())
+}
+
+fn print_output(journal: &[u8]) -> Result<()> {
+    let output = Output::abi_decode(journal)?;
+    println!("hash:           {}", hex::encode(output.hash));
+    println!("env_commitment: {}", hex::encode(output.env_commitment));
+    println!("chain_id:       {}", output.chain_id);
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use alloy_primitives::B256;
+use serde_json::json;
+use url::Url;
+
+/// Minimal client of the Ethereum JSON-RPC API, for the few calls not done through steel.
+#[derive(Clone)]
+pub struct EthRpcClient {
+    url: Url,
+    http: reqwest::Client,
+}
+
+impl EthRpcClient {
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Sends a JSON-RPC request and returns its result.
+    pub async fn call(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let mut response: serde_json::Value = self
+            .http
+            .post(self.url.clone())
+            .json(&json!({"jsonrpc": "2.0", "method": method, "params": params, "id": 1}))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if let Some(error) = response.get("error") {
+            anyhow::bail!("{} failed: {}", method, error);
+        }
+        match response.get_mut("result") {
+            Some(result) => Ok(result.take()),
+            None => anyhow::bail!("unexpected {} response: {}", method, response),
+        }
+    }
+
+    /// Number of the latest block.
+    pub async fn block_number(&self) -> anyhow::Result<u64> {
+        let result = self.call("eth_blockNumber", json!([])).await?;
+        parse_quantity(&result)
+    }
+
+    /// Hash of the canonical block with the given number, `None` if there is no such block yet.
+    pub async fn block_hash(&self, number: u64) -> anyhow::Result<Option<B256>> {
+        let block = self
+            .call(
+                "eth_getBlockByNumber",
+                json!([format!("0x{:x}", number), false]),
+            )
+            .await?;
+        if block.is_null() {
+            return Ok(None);
+        }
+        let hash = block
+            .get("hash")
+            .and_then(|hash| hash.as_str())
+            .ok_or_else(|| {
+                anyhow::anyhow!("unexpected eth_getBlockByNumber response: {}", block)
+            })?;
+        Ok(Some(hash.parse()?))
+    }
+
+    /// ID of the chain the endpoint serves.
+    pub async fn chain_id(&self) -> anyhow::Result<u64> {
+        let result = self.call("eth_chainId", json!([])).await?;
+        parse_quantity(&result)
+    }
+}
+
+/// Minimal client of the Ethereum beacon node API, used to check the endpoint steel
+/// fetches the beacon block roots from.
+#[derive(Clone)]
+pub struct BeaconApiClient {
+    url: Url,
+    http: reqwest::Client,
+}
+
+impl BeaconApiClient {
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Slot of the head block header.
+    pub async fn head_slot(&self) -> anyhow::Result<u64> {
+        let response: serde_json::Value = self
+            .http
+            .get(self.url.join("eth/v1/beacon/headers/head")?)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let slot = response
+            .pointer("/data/header/message/slot")
+            .and_then(|slot| slot.as_str())
+            .ok_or_else(|| anyhow::anyhow!("unexpected beacon header response: {}", response))?;
+        Ok(slot.parse()?)
+    }
+}
+
+/// Parses a hex encoded JSON-RPC quantity.
+pub fn parse_quantity(value: &serde_json::Value) -> anyhow::Result<u64> {
+    let quantity = value
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("expected a hex quantity, got {}", value))?;
+    Ok(u64::from_str_radix(quantity.trim_start_matches("0x"), 16)?)
+}
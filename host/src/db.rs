@@ -1,43 +1,251 @@
 use std::sync::Arc;
 
 use anyhow::Result;
-use sqlx::{Pool, Postgres, Row};
+use sqlx::{postgres::PgRow, Pool, Postgres, Row};
 use tokio::sync::Mutex;
 
+use crate::service::{
+    AggregationStatus, AnchorPolicy, ExecutionStats, ProofState, ProofStats, ProofStatus,
+    ReceiptKind,
+};
+
+/// Schema of the database, also run by the postgres container when its volume is created.
+const SCHEMA: &str = include_str!("../../init.sql");
+
+/// Creates the tables, and adds the columns missing from databases created by older versions.
+pub async fn migrate(db_pool: Arc<Mutex<Pool<Postgres>>>) -> Result<()> {
+    let db_lock = db_pool.lock().await;
+
+    sqlx::raw_sql(SCHEMA).execute(&*db_lock).await?;
+    Ok(())
+}
+
+/// A blob proof request as stored in the database.
+pub struct BlobProofRecord {
+    pub blob_id: String,
+    /// Hex encoded proof, existing only if the proof was generated.
+    pub proof: Option<String>,
+    /// Whether the proof generation failed.
+    pub failed: bool,
+    /// Whether the proof request was cancelled before being proven.
+    pub cancelled: bool,
+    /// Kind of receipt the proof is generated from.
+    pub receipt_kind: ReceiptKind,
+    /// Whether the proof is a fake dev mode proof.
+    pub fake: bool,
+    /// Policy the anchor block of the proof is chosen with.
+    pub anchor_policy: AnchorPolicy,
+    /// Block the guest input is anchored to, existing only once it was built.
+    pub anchor_block: Option<u64>,
+    /// Last block the proof can be verified onchain at, existing only once the input was built.
+    pub expiry_block: Option<u64>,
+    /// Whether the proof was marked as consumed.
+    pub consumed: bool,
+    /// Hex encoded hash of the block the guest input is anchored to, existing only once it was built.
+    pub anchor_hash: Option<String>,
+    /// Number of times the proof was invalidated by a reorg of its anchor block.
+    pub reorgs: u32,
+    /// Statistics of the proof generation, existing only if the proof was generated.
+    pub stats: Option<ProofStats>,
+}
+
+impl BlobProofRecord {
+    fn from_row(row: PgRow) -> Self {
+        Self {
+            blob_id: row.get("blob_id"),
+            proof: row.get("proof"),
+            failed: flag_from_row(&row, "failed"),
+            cancelled: flag_from_row(&row, "cancelled"),
+            receipt_kind: receipt_kind_from_row(&row),
+            fake: flag_from_row(&row, "fake"),
+            anchor_policy: anchor_policy_from_row(&row),
+            anchor_block: row
+                .get::<Option<i64>, _>("anchor_block")
+                .map(|anchor_block| anchor_block as u64),
+            expiry_block: row
+                .get::<Option<i64>, _>("expiry_block")
+                .map(|expiry_block| expiry_block as u64),
+            consumed: flag_from_row(&row, "consumed"),
+            anchor_hash: row.get("anchor_hash"),
+            reorgs: row.get::<Option<i32>, _>("reorgs").unwrap_or_default() as u32,
+            stats: proof_stats_from_row(&row),
+        }
+    }
+
+    /// Status of the proof request, as returned by the API.
+    pub fn into_status(self) -> ProofStatus {
+        ProofStatus {
+            state: self.state(),
+            blob_id: self.blob_id,
+            proof: self.proof,
+            receipt_kind: self.receipt_kind,
+            fake: self.fake,
+            anchor_policy: self.anchor_policy,
+            anchor_block: self.anchor_block,
+            expiry_block: self.expiry_block,
+            // Depends on the latest block, set by the service when returning the status
+            freshness: None,
+            consumed: self.consumed,
+            reorgs: self.reorgs,
+            stats: self.stats,
+        }
+    }
+
+    /// Derives the state of the proof request from its columns.
+    pub fn state(&self) -> ProofState {
+        if self.failed {
+            ProofState::Failed
+        } else if self.proof.is_some() {
+            ProofState::Proven
+        } else if self.cancelled {
+            ProofState::Cancelled
+        } else {
+            ProofState::Queued
+        }
+    }
+}
+
+// The boolean columns are nullable, rows stored before they were added may not have them set
+fn flag_from_row(row: &PgRow, column: &str) -> bool {
+    row.get::<Option<bool>, _>(column).unwrap_or_default()
+}
+
+fn receipt_kind_from_row(row: &PgRow) -> ReceiptKind {
+    row.get::<Option<String>, _>("receipt_kind")
+        .and_then(|receipt_kind| receipt_kind.parse().ok())
+        .unwrap_or_default()
+}
+
+fn anchor_policy_from_row(row: &PgRow) -> AnchorPolicy {
+    row.get::<Option<String>, _>("anchor_policy")
+        .and_then(|anchor_policy| anchor_policy.parse().ok())
+        .unwrap_or_default()
+}
+
+fn proof_stats_from_row(row: &PgRow) -> Option<ProofStats> {
+    let payload_size: Option<i64> = row.get("payload_size");
+    let proving_secs: Option<f64> = row.get("proving_secs");
+    let total_cycles: Option<i64> = row.get("total_cycles");
+    let execution = total_cycles.map(|total_cycles| ExecutionStats {
+        total_cycles: total_cycles as u64,
+        user_cycles: row.get::<Option<i64>, _>("user_cycles").unwrap_or_default() as u64,
+        paging_cycles: row
+            .get::<Option<i64>, _>("paging_cycles")
+            .unwrap_or_default() as u64,
+        segments: row.get::<Option<i64>, _>("segments").unwrap_or_default() as u64,
+    });
+    Some(ProofStats {
+        payload_size: payload_size? as u64,
+        proving_secs: proving_secs.unwrap_or_default(),
+        execution,
+    })
+}
+
+/// A proof request waiting to be proven.
+pub struct PendingProof {
+    pub blob_id: String,
+    /// Hex encoded ABI `EigenDACert`, if it was given with the request.
+    pub eigenda_cert: Option<String>,
+    /// Hex encoded payload, if it was given with the request.
+    pub payload: Option<String>,
+    /// JSON encoded receipt, if the guest was already proven and only the
+    /// compression to Groth16 is pending.
+    pub receipt: Option<String>,
+    /// Kind of receipt requested.
+    pub receipt_kind: ReceiptKind,
+    /// Policy the block the guest input is anchored to is chosen with.
+    pub anchor_policy: AnchorPolicy,
+    /// JSON encoded guest input built by a previous attempt, if any.
+    pub guest_input: Option<String>,
+    /// Number of times the request was queued again by a reorg. What an attempt stores is
+    /// discarded if it changes in the meantime, as it may come from the reorged block.
+    pub reorgs: u32,
+}
+
 /// Retrieves the next pending proof from the database.
 pub async fn retrieve_next_pending_proof(
     db_pool: Arc<Mutex<Pool<Postgres>>>,
-) -> Result<Option<String>> {
+) -> Result<Option<PendingProof>> {
     let db_lock = db_pool.lock().await;
     let pending_proof = sqlx::query(
         r#"
-        SELECT BLOB_ID FROM BLOB_PROOFS 
+        SELECT BLOB_ID, EIGENDA_CERT, PAYLOAD, RECEIPT, RECEIPT_KIND, ANCHOR_POLICY,
+        GUEST_INPUT, REORGS FROM BLOB_PROOFS 
         WHERE PROOF IS NULL
         AND FAILED IS NOT TRUE
+        AND CANCELLED IS NOT TRUE
         ORDER BY ID ASC LIMIT 1;
         "#,
     )
     .fetch_optional(&*db_lock)
     .await?;
 
-    let blob_id = pending_proof.map(|row| row.get("blob_id"));
-    Ok(blob_id)
+    let pending_proof = pending_proof.map(|row| PendingProof {
+        blob_id: row.get("blob_id"),
+        eigenda_cert: row.get("eigenda_cert"),
+        payload: row.get("payload"),
+        receipt: row.get("receipt"),
+        receipt_kind: receipt_kind_from_row(&row),
+        anchor_policy: anchor_policy_from_row(&row),
+        guest_input: row.get("guest_input"),
+        reorgs: row.get::<Option<i32>, _>("reorgs").unwrap_or_default() as u32,
+    });
+    Ok(pending_proof)
+}
+
+/// Retrieves the proof request for the blob, whatever its status.
+pub async fn retrieve_proof_request(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    blob_id: String,
+) -> Result<Option<PendingProof>> {
+    let db_lock = db_pool.lock().await;
+    let proof_request = sqlx::query(
+        r#"
+        SELECT BLOB_ID, EIGENDA_CERT, PAYLOAD, RECEIPT, RECEIPT_KIND, ANCHOR_POLICY,
+        GUEST_INPUT, REORGS FROM BLOB_PROOFS
+        WHERE BLOB_ID = $1
+        "#,
+    )
+    .bind(blob_id)
+    .fetch_optional(&*db_lock)
+    .await?;
+
+    let proof_request = proof_request.map(|row| PendingProof {
+        blob_id: row.get("blob_id"),
+        eigenda_cert: row.get("eigenda_cert"),
+        payload: row.get("payload"),
+        receipt: row.get("receipt"),
+        receipt_kind: receipt_kind_from_row(&row),
+        anchor_policy: anchor_policy_from_row(&row),
+        guest_input: row.get("guest_input"),
+        reorgs: row.get::<Option<i32>, _>("reorgs").unwrap_or_default() as u32,
+    });
+    Ok(proof_request)
 }
 
-/// Persists the blob proof request in the database.
+/// Persists the blob proof request in the database,
+/// along with the hex encoded ABI `EigenDACert` and payload if the caller provided them.
 pub async fn store_blob_proof_request(
     db_pool: Arc<Mutex<Pool<Postgres>>>,
     blob_id: String,
+    eigenda_cert: Option<String>,
+    payload: Option<String>,
+    receipt_kind: ReceiptKind,
+    anchor_policy: AnchorPolicy,
 ) -> Result<()> {
     let db_lock = db_pool.lock().await;
 
     sqlx::query(
         r#"
-        INSERT INTO BLOB_PROOFS (BLOB_ID)
-        VALUES ($1)
+        INSERT INTO BLOB_PROOFS (BLOB_ID, EIGENDA_CERT, PAYLOAD, RECEIPT_KIND, ANCHOR_POLICY)
+        VALUES ($1, $2, $3, $4, $5)
         "#,
     )
     .bind(blob_id)
+    .bind(eigenda_cert)
+    .bind(payload)
+    .bind(receipt_kind.as_str())
+    .bind(anchor_policy.to_string())
     .execute(&*db_lock)
     .await?;
     Ok(())
@@ -65,46 +273,123 @@ pub async fn proof_request_exists(
     Ok(exists)
 }
 
-/// Stores the blob generated proof in the database.
-pub async fn store_blob_proof(
+/// Stores the JSON encoded guest input of the blob in the database, along with the number
+/// and hash of the block it is anchored to, so retries don't need to fetch it from the network
+/// again, and the last block the proof built from it can be verified onchain at.
+pub async fn store_guest_input(
     db_pool: Arc<Mutex<Pool<Postgres>>>,
     blob_id: String,
-    proof: String,
+    guest_input: String,
+    anchor_block: u64,
+    anchor_hash: String,
+    expiry_block: u64,
+    reorgs: u32,
 ) -> Result<()> {
     let db_lock = db_pool.lock().await;
 
     sqlx::query(
         r#"
         UPDATE BLOB_PROOFS
-        SET PROOF = $1
-        WHERE BLOB_ID = $2
+        SET GUEST_INPUT = $1, ANCHOR_BLOCK = $2, ANCHOR_HASH = $3, EXPIRY_BLOCK = $4
+        WHERE BLOB_ID = $5
+        AND COALESCE(REORGS, 0) = $6
         "#,
     )
-    .bind(proof)
+    .bind(guest_input)
+    .bind(anchor_block as i64)
+    .bind(anchor_hash)
+    .bind(expiry_block as i64)
     .bind(blob_id)
+    .bind(reorgs as i32)
     .execute(&*db_lock)
     .await?;
     Ok(())
 }
 
-/// Retrieves the blob proof from the database.
+/// Stores the JSON encoded receipt of the blob in the database, along with the
+/// statistics of its generation, so a failed compression doesn't need to prove the guest again.
+pub async fn store_blob_receipt(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    blob_id: String,
+    receipt: String,
+    stats: &ProofStats,
+    reorgs: u32,
+) -> Result<()> {
+    let db_lock = db_pool.lock().await;
+
+    let execution = stats.execution.as_ref();
+    sqlx::query(
+        r#"
+        UPDATE BLOB_PROOFS
+        SET RECEIPT = $1, PAYLOAD_SIZE = $2, PROVING_SECS = $3,
+        TOTAL_CYCLES = $4, USER_CYCLES = $5, PAGING_CYCLES = $6, SEGMENTS = $7
+        WHERE BLOB_ID = $8
+        AND COALESCE(REORGS, 0) = $9
+        "#,
+    )
+    .bind(receipt)
+    .bind(stats.payload_size as i64)
+    .bind(stats.proving_secs)
+    .bind(execution.map(|execution| execution.total_cycles as i64))
+    .bind(execution.map(|execution| execution.user_cycles as i64))
+    .bind(execution.map(|execution| execution.paging_cycles as i64))
+    .bind(execution.map(|execution| execution.segments as i64))
+    .bind(blob_id)
+    .bind(reorgs as i32)
+    .execute(&*db_lock)
+    .await?;
+    Ok(())
+}
+
+/// Stores the blob generated proof in the database, flagging it if it's a fake dev mode proof.
+/// Returns `false` if the proof request was cancelled while it was being proven, in which case
+/// the proof is discarded so that the request stays cancelled, or if it was queued again by a
+/// reorg since `reorgs` was read, in which case the proof may be anchored to the reorged block.
+pub async fn store_blob_proof(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    blob_id: String,
+    proof: String,
+    fake: bool,
+    reorgs: u32,
+) -> Result<bool> {
+    let db_lock = db_pool.lock().await;
+
+    let result = sqlx::query(
+        r#"
+        UPDATE BLOB_PROOFS
+        SET PROOF = $1, FAKE = $2
+        WHERE BLOB_ID = $3
+        AND CANCELLED IS NOT TRUE
+        AND COALESCE(REORGS, 0) = $4
+        "#,
+    )
+    .bind(proof)
+    .bind(fake)
+    .bind(blob_id)
+    .bind(reorgs as i32)
+    .execute(&*db_lock)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Retrieves the blob proof request from the database.
 /// Returns an Option wrapped by a `Result`
 /// that may contain:
-/// - `None` if the proof does not exist
-/// - `Some((proof, failed))` if the proof exists
-///   where proof is an optional `String`, existing only if the proof was generated,
-///   and failed is a boolean indicating if the proof generation failed.
+/// - `None` if the proof request does not exist
+/// - `Some(record)` if the proof request exists, see [`BlobProofRecord`].
 ///
 /// In case the query fails, it returns an `Err`.
 pub async fn retrieve_blob_id_proof(
     db_pool: Arc<Mutex<Pool<Postgres>>>,
     blob_id: String,
-) -> Result<Option<(Option<String>, bool)>> {
+) -> Result<Option<BlobProofRecord>> {
     let db_lock = db_pool.lock().await;
 
     let row = sqlx::query(
         r#"
-            SELECT PROOF, FAILED FROM BLOB_PROOFS
+            SELECT BLOB_ID, PROOF, FAILED, CANCELLED, RECEIPT_KIND, FAKE, ANCHOR_POLICY,
+            ANCHOR_BLOCK, EXPIRY_BLOCK, CONSUMED, ANCHOR_HASH, REORGS, PAYLOAD_SIZE, PROVING_SECS,
+            TOTAL_CYCLES, USER_CYCLES, PAGING_CYCLES, SEGMENTS FROM BLOB_PROOFS
             WHERE BLOB_ID = $1
             "#,
     )
@@ -112,30 +397,494 @@ pub async fn retrieve_blob_id_proof(
     .fetch_optional(&*db_lock)
     .await?;
 
-    let result = row.map(|row| {
-        let proof: Option<String> = row.get("proof");
-        let failed: bool = row.get("failed");
-        (proof, failed)
-    });
-
-    Ok(result)
+    Ok(row.map(BlobProofRecord::from_row))
 }
 
 /// Marks a blob proof request as failed in the database.
+/// Returns `false` if the proof request was cancelled while it was being proven,
+/// in which case it stays cancelled.
 pub async fn mark_blob_proof_request_failed(
     db_pool: Arc<Mutex<Pool<Postgres>>>,
     blob_id: String,
-) -> Result<()> {
+) -> Result<bool> {
     let db_lock = db_pool.lock().await;
 
-    sqlx::query(
+    let result = sqlx::query(
         r#"
             UPDATE BLOB_PROOFS
             SET FAILED = TRUE
             WHERE BLOB_ID = $1
+            AND CANCELLED IS NOT TRUE
+            "#,
+    )
+    .bind(blob_id)
+    .execute(&*db_lock)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Retrieves the blob proof requests in the given state (or all of them if no
+/// state is given), oldest first.
+pub async fn retrieve_blob_proofs(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    state: Option<ProofState>,
+    limit: i64,
+) -> Result<Vec<BlobProofRecord>> {
+    // Same precedence as `BlobProofRecord::state`: failed, proven, cancelled and queued
+    let filter = match state {
+        None => "TRUE",
+        Some(ProofState::Failed) => "FAILED IS TRUE",
+        Some(ProofState::Proven) => "FAILED IS NOT TRUE AND PROOF IS NOT NULL",
+        Some(ProofState::Cancelled) => "FAILED IS NOT TRUE AND PROOF IS NULL AND CANCELLED IS TRUE",
+        Some(ProofState::Queued) => {
+            "FAILED IS NOT TRUE AND PROOF IS NULL AND CANCELLED IS NOT TRUE"
+        }
+    };
+
+    let db_lock = db_pool.lock().await;
+
+    let rows = sqlx::query(&format!(
+        r#"
+            SELECT BLOB_ID, PROOF, FAILED, CANCELLED, RECEIPT_KIND, FAKE, ANCHOR_POLICY,
+            ANCHOR_BLOCK, EXPIRY_BLOCK, CONSUMED, ANCHOR_HASH, REORGS, PAYLOAD_SIZE, PROVING_SECS,
+            TOTAL_CYCLES, USER_CYCLES, PAGING_CYCLES, SEGMENTS FROM BLOB_PROOFS
+            WHERE {}
+            ORDER BY ID ASC LIMIT $1
+            "#,
+        filter
+    ))
+    .bind(limit)
+    .fetch_all(&*db_lock)
+    .await?;
+
+    Ok(rows.into_iter().map(BlobProofRecord::from_row).collect())
+}
+
+/// Marks a queued blob proof request as cancelled, so the prover thread skips it.
+/// Returns `false` if there is no queued proof request for the blob.
+pub async fn cancel_blob_proof_request(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    blob_id: String,
+) -> Result<bool> {
+    let db_lock = db_pool.lock().await;
+
+    let result = sqlx::query(
+        r#"
+            UPDATE BLOB_PROOFS
+            SET CANCELLED = TRUE
+            WHERE BLOB_ID = $1
+            AND PROOF IS NULL
+            AND FAILED IS NOT TRUE
+            AND CANCELLED IS NOT TRUE
+            "#,
+    )
+    .bind(blob_id)
+    .execute(&*db_lock)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Puts a failed, cancelled or fake proven blob proof request back in the queue.
+/// The stored succinct receipt is kept, so only its compression runs again,
+/// unless it is fake. Returns `false` if there is no such proof request for the blob.
+pub async fn retry_blob_proof_request(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    blob_id: String,
+) -> Result<bool> {
+    let db_lock = db_pool.lock().await;
+
+    let result = sqlx::query(
+        r#"
+            UPDATE BLOB_PROOFS
+            SET FAILED = FALSE, CANCELLED = FALSE, PROOF = NULL, FAKE = FALSE,
+            RECEIPT = CASE WHEN FAKE IS TRUE THEN NULL ELSE RECEIPT END,
+            PAYLOAD_SIZE = CASE WHEN FAKE IS TRUE THEN NULL ELSE PAYLOAD_SIZE END
+            WHERE BLOB_ID = $1
+            AND (FAILED IS TRUE OR CANCELLED IS TRUE OR FAKE IS TRUE)
+            "#,
+    )
+    .bind(blob_id)
+    .execute(&*db_lock)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Puts a proven blob proof request back in the queue to compress its stored succinct
+/// receipt again, e.g. after the Groth16 verifier parameters changed.
+/// Returns `false` if there is no proven Groth16 request with a real stored receipt for the blob.
+pub async fn recompress_blob_proof(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    blob_id: String,
+) -> Result<bool> {
+    let db_lock = db_pool.lock().await;
+
+    let result = sqlx::query(
+        r#"
+            UPDATE BLOB_PROOFS
+            SET PROOF = NULL
+            WHERE BLOB_ID = $1
+            AND PROOF IS NOT NULL
+            AND RECEIPT IS NOT NULL
+            AND RECEIPT_KIND = 'groth16'
+            AND FAILED IS NOT TRUE
+            AND FAKE IS NOT TRUE
+            "#,
+    )
+    .bind(blob_id)
+    .execute(&*db_lock)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Marks the proof of a proven blob proof request as consumed, so it's not re-proven when
+/// it expires. Returns `false` if there is no proven request for the blob.
+pub async fn mark_blob_proof_consumed(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    blob_id: String,
+) -> Result<bool> {
+    let db_lock = db_pool.lock().await;
+
+    let result = sqlx::query(
+        r#"
+            UPDATE BLOB_PROOFS
+            SET CONSUMED = TRUE
+            WHERE BLOB_ID = $1
+            AND PROOF IS NOT NULL
+            AND FAILED IS NOT TRUE
+            "#,
+    )
+    .bind(blob_id)
+    .execute(&*db_lock)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Retrieves the proven, unconsumed, real Groth16 blob proofs that expire at or before the
+/// given block, oldest first. Only proofs with an anchor policy relative to the latest block
+/// are retrieved, proving the others again would give the same proof.
+pub async fn retrieve_expiring_blob_proofs(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    expiry_block: u64,
+    limit: i64,
+) -> Result<Vec<BlobProofRecord>> {
+    let db_lock = db_pool.lock().await;
+
+    let rows = sqlx::query(
+        r#"
+            SELECT BLOB_ID, PROOF, FAILED, CANCELLED, RECEIPT_KIND, FAKE, ANCHOR_POLICY,
+            ANCHOR_BLOCK, EXPIRY_BLOCK, CONSUMED, ANCHOR_HASH, REORGS, PAYLOAD_SIZE, PROVING_SECS,
+            TOTAL_CYCLES, USER_CYCLES, PAGING_CYCLES, SEGMENTS FROM BLOB_PROOFS
+            WHERE PROOF IS NOT NULL
+            AND FAILED IS NOT TRUE
+            AND FAKE IS NOT TRUE
+            AND CONSUMED IS NOT TRUE
+            AND RECEIPT_KIND = 'groth16'
+            AND ANCHOR_POLICY LIKE 'latest%'
+            AND EXPIRY_BLOCK <= $1
+            ORDER BY ID ASC LIMIT $2
+            "#,
+    )
+    .bind(expiry_block as i64)
+    .bind(limit)
+    .fetch_all(&*db_lock)
+    .await?;
+
+    Ok(rows.into_iter().map(BlobProofRecord::from_row).collect())
+}
+
+/// Puts a proven, unconsumed blob proof request back in the queue to prove it again from
+/// scratch, discarding its proof and receipt. The stored guest input is kept, so only its
+/// EVM state is fetched again. Returns `false` if there is no such proof request for the blob.
+pub async fn reprove_blob_proof(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    blob_id: String,
+) -> Result<bool> {
+    let db_lock = db_pool.lock().await;
+
+    let result = sqlx::query(
+        r#"
+            UPDATE BLOB_PROOFS
+            SET PROOF = NULL, RECEIPT = NULL, PAYLOAD_SIZE = NULL
+            WHERE BLOB_ID = $1
+            AND PROOF IS NOT NULL
+            AND FAILED IS NOT TRUE
+            AND CONSUMED IS NOT TRUE
+            "#,
+    )
+    .bind(blob_id)
+    .execute(&*db_lock)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Retrieves the proven, unconsumed blob proofs, and the queued requests with a stored guest
+/// input, anchored at or after the given block and still verifiable at it, oldest first.
+pub async fn retrieve_recently_anchored_blob_proofs(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    from_block: u64,
+    limit: i64,
+) -> Result<Vec<BlobProofRecord>> {
+    let db_lock = db_pool.lock().await;
+
+    let rows = sqlx::query(
+        r#"
+            SELECT BLOB_ID, PROOF, FAILED, CANCELLED, RECEIPT_KIND, FAKE, ANCHOR_POLICY,
+            ANCHOR_BLOCK, EXPIRY_BLOCK, CONSUMED, ANCHOR_HASH, REORGS, PAYLOAD_SIZE, PROVING_SECS,
+            TOTAL_CYCLES, USER_CYCLES, PAGING_CYCLES, SEGMENTS FROM BLOB_PROOFS
+            WHERE (PROOF IS NOT NULL OR GUEST_INPUT IS NOT NULL)
+            AND FAILED IS NOT TRUE
+            AND CANCELLED IS NOT TRUE
+            AND CONSUMED IS NOT TRUE
+            AND ANCHOR_HASH IS NOT NULL
+            AND ANCHOR_BLOCK >= $1
+            AND EXPIRY_BLOCK >= $1
+            ORDER BY ID ASC LIMIT $2
+            "#,
+    )
+    .bind(from_block as i64)
+    .bind(limit)
+    .fetch_all(&*db_lock)
+    .await?;
+
+    Ok(rows.into_iter().map(BlobProofRecord::from_row).collect())
+}
+
+/// Invalidates the proof of a blob whose anchor block was reorged out, and puts its request back
+/// in the queue to prove it from scratch. The stored guest input and receipt are discarded, also
+/// for requests still queued, as they belong to the reorged block. Returns `false` if the request
+/// is no longer anchored to the given block hash, or was consumed or cancelled in the meantime.
+pub async fn requeue_reorged_blob_proof(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    blob_id: String,
+    anchor_hash: String,
+) -> Result<bool> {
+    let db_lock = db_pool.lock().await;
+
+    let result = sqlx::query(
+        r#"
+            UPDATE BLOB_PROOFS
+            SET PROOF = NULL, RECEIPT = NULL, PAYLOAD_SIZE = NULL, GUEST_INPUT = NULL,
+            ANCHOR_BLOCK = NULL, ANCHOR_HASH = NULL, EXPIRY_BLOCK = NULL,
+            REORGS = COALESCE(REORGS, 0) + 1
+            WHERE BLOB_ID = $1
+            AND ANCHOR_HASH = $2
+            AND (PROOF IS NOT NULL OR GUEST_INPUT IS NOT NULL)
+            AND FAILED IS NOT TRUE
+            AND CANCELLED IS NOT TRUE
+            AND CONSUMED IS NOT TRUE
             "#,
     )
     .bind(blob_id)
+    .bind(anchor_hash)
+    .execute(&*db_lock)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Retrieves the stored succinct receipts of the given blobs, along with their blob IDs.
+/// Blobs without a proof request are left out, and blobs without a stored receipt get `None`.
+pub async fn retrieve_blob_receipts(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    blob_ids: Vec<String>,
+) -> Result<Vec<(String, Option<String>)>> {
+    let db_lock = db_pool.lock().await;
+
+    let rows = sqlx::query(
+        r#"
+            SELECT BLOB_ID, RECEIPT FROM BLOB_PROOFS
+            WHERE BLOB_ID = ANY($1)
+            "#,
+    )
+    .bind(blob_ids)
+    .fetch_all(&*db_lock)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.get("blob_id"), row.get("receipt")))
+        .collect())
+}
+
+/// An aggregation request as stored in the database.
+pub struct AggregationRecord {
+    pub id: i32,
+    /// Blob IDs of the aggregated proofs, in the order they are aggregated.
+    pub blob_ids: Vec<String>,
+    /// Hex encoded aggregated proof, existing only if it was generated.
+    pub proof: Option<String>,
+    /// Whether the aggregation failed.
+    pub failed: bool,
+    /// Whether the aggregated proof is a fake dev mode proof.
+    pub fake: bool,
+}
+
+impl AggregationRecord {
+    /// Status of the aggregation request, as returned by the API.
+    pub fn into_status(self) -> AggregationStatus {
+        let state = if self.failed {
+            ProofState::Failed
+        } else if self.proof.is_some() {
+            ProofState::Proven
+        } else {
+            ProofState::Queued
+        };
+        AggregationStatus {
+            aggregation_id: self.id as u64,
+            blob_ids: self.blob_ids,
+            state,
+            proof: self.proof,
+            fake: self.fake,
+        }
+    }
+}
+
+/// An aggregation request waiting to be proven.
+pub struct PendingAggregation {
+    pub id: i32,
+    pub blob_ids: Vec<String>,
+}
+
+/// Stores a request to aggregate the proofs of the given blobs, returning its ID.
+pub async fn store_aggregation_request(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    blob_ids: Vec<String>,
+) -> Result<i32> {
+    let db_lock = db_pool.lock().await;
+
+    let id = sqlx::query(
+        r#"
+            INSERT INTO AGGREGATIONS (BLOB_IDS)
+            VALUES ($1)
+            RETURNING ID
+            "#,
+    )
+    .bind(blob_ids)
+    .fetch_one(&*db_lock)
+    .await?
+    .get("id");
+    Ok(id)
+}
+
+/// Retrieves the aggregation request from the database, `None` if it does not exist.
+pub async fn retrieve_aggregation(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    id: i32,
+) -> Result<Option<AggregationRecord>> {
+    let db_lock = db_pool.lock().await;
+
+    let row = sqlx::query(
+        r#"
+            SELECT ID, BLOB_IDS, PROOF, FAILED, FAKE FROM AGGREGATIONS
+            WHERE ID = $1
+            "#,
+    )
+    .bind(id)
+    .fetch_optional(&*db_lock)
+    .await?;
+
+    Ok(row.map(|row| AggregationRecord {
+        id: row.get("id"),
+        blob_ids: row.get("blob_ids"),
+        proof: row.get("proof"),
+        failed: flag_from_row(&row, "failed"),
+        fake: flag_from_row(&row, "fake"),
+    }))
+}
+
+/// Retrieves the next aggregation request waiting to be proven, oldest first.
+pub async fn retrieve_next_pending_aggregation(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+) -> Result<Option<PendingAggregation>> {
+    let db_lock = db_pool.lock().await;
+
+    let row = sqlx::query(
+        r#"
+            SELECT ID, BLOB_IDS FROM AGGREGATIONS
+            WHERE PROOF IS NULL
+            AND FAILED IS NOT TRUE
+            ORDER BY ID ASC LIMIT 1
+            "#,
+    )
+    .fetch_optional(&*db_lock)
+    .await?;
+
+    Ok(row.map(|row| PendingAggregation {
+        id: row.get("id"),
+        blob_ids: row.get("blob_ids"),
+    }))
+}
+
+/// Stores the generated aggregated proof, flagging it if it's a fake dev mode proof.
+pub async fn store_aggregation_proof(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    id: i32,
+    proof: String,
+    fake: bool,
+) -> Result<()> {
+    let db_lock = db_pool.lock().await;
+
+    sqlx::query(
+        r#"
+            UPDATE AGGREGATIONS
+            SET PROOF = $1, FAKE = $2
+            WHERE ID = $3
+            "#,
+    )
+    .bind(proof)
+    .bind(fake)
+    .bind(id)
+    .execute(&*db_lock)
+    .await?;
+    Ok(())
+}
+
+/// Marks an aggregation request as failed in the database.
+pub async fn mark_aggregation_failed(db_pool: Arc<Mutex<Pool<Postgres>>>, id: i32) -> Result<()> {
+    let db_lock = db_pool.lock().await;
+
+    sqlx::query(
+        r#"
+            UPDATE AGGREGATIONS
+            SET FAILED = TRUE
+            WHERE ID = $1
+            "#,
+    )
+    .bind(id)
+    .execute(&*db_lock)
+    .await?;
+    Ok(())
+}
+
+/// Retrieves whether the proof generation worker was paused through the admin methods.
+pub async fn retrieve_worker_paused(db_pool: Arc<Mutex<Pool<Postgres>>>) -> Result<bool> {
+    let db_lock = db_pool.lock().await;
+
+    let paused = sqlx::query(
+        r#"
+            SELECT PAUSED FROM WORKER_STATE
+            WHERE ID = 1
+            "#,
+    )
+    .fetch_optional(&*db_lock)
+    .await?
+    .map(|row| flag_from_row(&row, "paused"))
+    .unwrap_or(false);
+
+    Ok(paused)
+}
+
+/// Persists whether the proof generation worker is paused, so restarts honour it.
+pub async fn store_worker_paused(db_pool: Arc<Mutex<Pool<Postgres>>>, paused: bool) -> Result<()> {
+    let db_lock = db_pool.lock().await;
+
+    sqlx::query(
+        r#"
+            INSERT INTO WORKER_STATE (ID, PAUSED)
+            VALUES (1, $1)
+            ON CONFLICT (ID) DO UPDATE SET PAUSED = EXCLUDED.PAUSED
+            "#,
+    )
+    .bind(paused)
     .execute(&*db_lock)
     .await?;
     Ok(())
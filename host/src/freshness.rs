@@ -0,0 +1,78 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use sqlx::{Pool, Postgres};
+use tokio::sync::Mutex;
+use url::Url;
+
+use crate::chain::EthRpcClient;
+use crate::db::{reprove_blob_proof, retrieve_expiring_blob_proofs};
+use crate::metrics::STALE_PROOFS_REPROVED_COUNTER;
+use crate::notifier::ProofNotifier;
+
+/// Time between two checks of the proofs about to expire.
+pub const FRESHNESS_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Maximum number of proofs queued to be proven again on each check.
+const MAX_REPROVED_PER_CHECK: i64 = 100;
+
+/// Queues the proofs that expire within `expiry_margin_blocks` of the latest block, and were
+/// not marked as consumed, to prove them again anchored to a newer block.
+pub struct FreshnessMonitor {
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    eth_rpc: EthRpcClient,
+    notifier: ProofNotifier,
+    expiry_margin_blocks: u64,
+}
+
+impl FreshnessMonitor {
+    pub fn new(
+        db_pool: Arc<Mutex<Pool<Postgres>>>,
+        rpc_url: Url,
+        notifier: ProofNotifier,
+        expiry_margin_blocks: u64,
+    ) -> Self {
+        Self {
+            db_pool,
+            eth_rpc: EthRpcClient::new(rpc_url),
+            notifier,
+            expiry_margin_blocks,
+        }
+    }
+
+    /// Runs the check loop. Failed checks are logged and retried on the next iteration.
+    pub async fn run(&self) -> Result<()> {
+        loop {
+            if let Err(e) = self.reprove_expiring_proofs().await {
+                tracing::warn!("Failed checking the freshness of the proofs: {}", e);
+            }
+            tokio::time::sleep(FRESHNESS_CHECK_INTERVAL).await;
+        }
+    }
+
+    async fn reprove_expiring_proofs(&self) -> Result<()> {
+        let latest_block = self.eth_rpc.block_number().await?;
+        let records = retrieve_expiring_blob_proofs(
+            self.db_pool.clone(),
+            latest_block + self.expiry_margin_blocks,
+            MAX_REPROVED_PER_CHECK,
+        )
+        .await?;
+        for record in records {
+            // Consumed in the meantime, or its request changed state
+            if !reprove_blob_proof(self.db_pool.clone(), record.blob_id.clone()).await? {
+                continue;
+            }
+            tracing::info!(
+                "Proof of Blob Id {} expires at block {}, queued to prove it again",
+                record.blob_id,
+                record.expiry_block.unwrap_or_default()
+            );
+            STALE_PROOFS_REPROVED_COUNTER.inc();
+            self.notifier
+                .notify_stored(self.db_pool.clone(), record.blob_id)
+                .await;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+use std::{pin::Pin, sync::Arc};
+
+use anyhow::Result;
+use client::{proof::is_fake_proof, types::InvalidAnchorPolicy};
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::ReceiverStream, Stream};
+use tonic::{transport::Server, Request, Response, Status};
+
+use crate::service::{
+    GenerateProofParams, ProofState, ProofStatus, ProvingService, ReceiptKind, ServiceError,
+};
+
+pub mod proto {
+    tonic::include_proto!("proving_service");
+}
+
+use proto::proving_service_server::{ProvingService as ProvingServiceApi, ProvingServiceServer};
+
+impl From<ServiceError> for Status {
+    fn from(err: ServiceError) -> Self {
+        match err {
+            ServiceError::InvalidParams(_) => Status::invalid_argument(err.to_string()),
+            ServiceError::AlreadySubmitted => Status::already_exists(err.to_string()),
+            ServiceError::NotFound | ServiceError::AggregationNotFound => {
+                Status::not_found(err.to_string())
+            }
+            ServiceError::Queued => Status::unavailable(err.to_string()),
+            ServiceError::Failed | ServiceError::InvalidState(_) => {
+                Status::failed_precondition(err.to_string())
+            }
+            ServiceError::Internal => Status::internal(err.to_string()),
+        }
+    }
+}
+
+impl From<ProofState> for proto::ProofState {
+    fn from(state: ProofState) -> Self {
+        match state {
+            ProofState::Queued => proto::ProofState::Queued,
+            ProofState::Proven => proto::ProofState::Proven,
+            ProofState::Failed => proto::ProofState::Failed,
+            ProofState::Cancelled => proto::ProofState::Cancelled,
+        }
+    }
+}
+
+impl From<ReceiptKind> for proto::ReceiptKind {
+    fn from(receipt_kind: ReceiptKind) -> Self {
+        match receipt_kind {
+            ReceiptKind::Composite => proto::ReceiptKind::Composite,
+            ReceiptKind::Succinct => proto::ReceiptKind::Succinct,
+            ReceiptKind::Groth16 => proto::ReceiptKind::Groth16,
+        }
+    }
+}
+
+/// Receipt kind of a submitted proof request, `None` (Groth16) if unspecified.
+fn requested_receipt_kind(receipt_kind: proto::ReceiptKind) -> Option<ReceiptKind> {
+    match receipt_kind {
+        proto::ReceiptKind::Unspecified => None,
+        proto::ReceiptKind::Composite => Some(ReceiptKind::Composite),
+        proto::ReceiptKind::Succinct => Some(ReceiptKind::Succinct),
+        proto::ReceiptKind::Groth16 => Some(ReceiptKind::Groth16),
+    }
+}
+
+fn decode_proof(proof: &str) -> Result<Vec<u8>, Status> {
+    hex::decode(proof).map_err(|_| Status::internal("Stored proof is not valid hex"))
+}
+
+fn to_proto_status(status: ProofStatus) -> Result<proto::ProofStatus, Status> {
+    let proof = match status.proof {
+        Some(proof) => decode_proof(&proof)?,
+        None => vec![],
+    };
+    Ok(proto::ProofStatus {
+        blob_id: status.blob_id,
+        state: proto::ProofState::from(status.state) as i32,
+        proof,
+        fake: status.fake,
+        receipt_kind: proto::ReceiptKind::from(status.receipt_kind) as i32,
+        anchor_policy: status.anchor_policy.to_string(),
+        anchor_block: status.anchor_block.unwrap_or_default(),
+        expiry_block: status.expiry_block.unwrap_or_default(),
+        freshness: status
+            .freshness
+            .map(|freshness| freshness.to_string())
+            .unwrap_or_default(),
+        consumed: status.consumed,
+        reorgs: status.reorgs,
+    })
+}
+
+/// gRPC front end of the [`ProvingService`] handlers.
+pub struct GrpcProvingService {
+    service: Arc<ProvingService>,
+}
+
+impl GrpcProvingService {
+    pub fn new(service: Arc<ProvingService>) -> Self {
+        Self { service }
+    }
+}
+
+#[tonic::async_trait]
+impl ProvingServiceApi for GrpcProvingService {
+    type StreamProofUpdatesStream =
+        Pin<Box<dyn Stream<Item = Result<proto::ProofStatus, Status>> + Send>>;
+
+    async fn submit_proof(
+        &self,
+        request: Request<proto::SubmitProofRequest>,
+    ) -> Result<Response<proto::ProofStatus>, Status> {
+        let request = request.into_inner();
+        let params = GenerateProofParams {
+            blob_id: request.blob_id,
+            eigenda_cert: (!request.eigenda_cert.is_empty())
+                .then(|| hex::encode(&request.eigenda_cert)),
+            payload: (!request.payload.is_empty()).then(|| hex::encode(&request.payload)),
+            receipt_kind: requested_receipt_kind(request.receipt_kind()),
+            anchor_policy: (!request.anchor_policy.is_empty())
+                .then(|| request.anchor_policy.parse())
+                .transpose()
+                .map_err(|e: InvalidAnchorPolicy| Status::invalid_argument(e.to_string()))?,
+        };
+        let status = self.service.generate_proof(params).await?;
+        Ok(Response::new(to_proto_status(status)?))
+    }
+
+    async fn get_proof_status(
+        &self,
+        request: Request<proto::BlobIdRequest>,
+    ) -> Result<Response<proto::ProofStatus>, Status> {
+        let status = self
+            .service
+            .get_proof_status(request.into_inner().blob_id)
+            .await?;
+        Ok(Response::new(to_proto_status(status)?))
+    }
+
+    async fn get_proof(
+        &self,
+        request: Request<proto::BlobIdRequest>,
+    ) -> Result<Response<proto::GetProofResponse>, Status> {
+        let proof = decode_proof(&self.service.get_proof(request.into_inner().blob_id).await?)?;
+        Ok(Response::new(proto::GetProofResponse {
+            fake: is_fake_proof(&proof),
+            proof,
+        }))
+    }
+
+    async fn stream_proof_updates(
+        &self,
+        request: Request<proto::BlobIdRequest>,
+    ) -> Result<Response<Self::StreamProofUpdatesStream>, Status> {
+        let blob_id = request.into_inner().blob_id;
+        // Fail the call itself if the blob was never submitted
+        let mut status = self.service.get_proof_status(blob_id.clone()).await?;
+
+        let (tx, rx) = mpsc::channel(4);
+        let service = self.service.clone();
+        tokio::spawn(async move {
+            loop {
+                let state = status.state;
+                if tx.send(to_proto_status(status)).await.is_err() {
+                    // The client went away
+                    return;
+                }
+                if state != ProofState::Queued {
+                    return;
+                }
+                let next = tokio::select! {
+                    next = service.await_proof_status(blob_id.clone(), None) => next,
+                    // The client went away
+                    _ = tx.closed() => return,
+                };
+                status = match next {
+                    Ok(next) => next,
+                    Err(e) => {
+                        let _ = tx.send(Err(e.into())).await;
+                        return;
+                    }
+                };
+            }
+        });
+
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
+
+/// Runs the gRPC server until it fails.
+pub async fn run_grpc_server(grpc_url: String, service: Arc<ProvingService>) -> Result<()> {
+    let addr = grpc_url.parse()?;
+    Server::builder()
+        .add_service(ProvingServiceServer::new(GrpcProvingService::new(service)))
+        .serve(addr)
+        .await?;
+    Ok(())
+}
@@ -1,57 +1,420 @@
-use alloy_primitives::Address;
-use alloy_sol_types::SolCall;
-use anyhow::Context;
+use std::{collections::BTreeMap, sync::Mutex as StdMutex};
+
+use alloy_primitives::{keccak256, Address, B256};
+use alloy_sol_types::{SolCall, SolValue};
+use ark_bn254::G1Affine;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use client::types::ReceiptKind;
+use common::chain::Chain;
+use common::output::{AggregatedBlob, AggregatedOutput, Output};
 use common::polynomial_form::PolynomialForm;
 use common::serializable_g1::SerializableG1;
 use common::verify_blob::IVerifyBlob;
-use methods::GUEST_ELF;
+use methods::{AGGREGATOR_ELF, GUEST_ELF};
 use risc0_steel::{
-    ethereum::{EthEvmEnv, ETH_HOLESKY_CHAIN_SPEC},
-    Contract,
+    ethereum::{
+        EthChainSpec, EthEvmEnv, EthEvmInput, ETH_HOLESKY_CHAIN_SPEC, ETH_MAINNET_CHAIN_SPEC,
+        ETH_SEPOLIA_CHAIN_SPEC,
+    },
+    Contract, EvmBlockHeader, EvmInput,
+};
+use risc0_zkvm::{
+    serde::{from_slice, to_vec},
+    sha::Digest,
+    ExecutorEnv, ExecutorImpl, NullSegmentRef, Receipt, Session,
 };
-use risc0_zkvm::ProveInfo;
-use risc0_zkvm::{default_prover, ExecutorEnv, ProverOpts, VerifierContext};
 use rust_eigenda_v2_common::{EigenDACert, Payload, PayloadForm};
 use rust_kzg_bn254_primitives::blob::Blob;
 use rust_kzg_bn254_primitives::helpers::compute_challenge;
 use rust_kzg_bn254_prover::kzg::KZG;
 use rust_kzg_bn254_prover::srs::SRS;
+use serde::{Deserialize, Serialize};
 use url::Url;
 
-pub async fn run_guest(
+use crate::prover::{ProveOutput, ProverBackend};
+use crate::service::decode_eigenda_cert;
+
+/// Checks that the payload matches the blob commitment of the EigenDA cert,
+/// so a payload supplied by the caller can be proven without retrieving it from the relays.
+pub fn verify_payload_commitment(
+    eigenda_cert: &EigenDACert,
+    data: &[u8],
+    payload_form: PayloadForm,
+    srs: &SRS,
+) -> anyhow::Result<()> {
+    let payload = Payload::new(data.to_vec());
+    let encoded_data = payload.to_blob(payload_form)?.serialize();
+    let blob = Blob::new(&encoded_data);
+
+    let mut kzg = KZG::new();
+    kzg.calculate_and_store_roots_of_unity(blob.len().try_into()?)?;
+
+    let commitment = kzg.commit_coeff_form(&blob.to_polynomial_coeff_form(), srs)?;
+    let cert_commitment = eigenda_cert
+        .blob_inclusion_info
+        .blob_certificate
+        .blob_header
+        .commitment
+        .commitment;
+
+    if commitment != cert_commitment {
+        anyhow::bail!("Payload does not match the blob commitment of the EigenDA cert");
+    }
+    Ok(())
+}
+
+/// Inputs of the guest, written to its stdin in the order it reads them.
+pub struct GuestInput {
+    pub evm_input: EthEvmInput,
+    pub eigenda_cert: EigenDACert,
+    pub data: Vec<u8>,
+    pub kzg_proof: SerializableG1,
+    pub cert_verifier_router_addr: Address,
+    pub polynomial_form: PolynomialForm,
+    pub chain: Chain,
+    /// Number of the block the `checkDACert` call was preflighted at, not passed to the guest.
+    pub anchor_block_number: u64,
+    /// Hash of the anchor block, not passed to the guest. Used to detect if it's reorged out.
+    pub anchor_block_hash: B256,
+}
+
+/// Number of recent block hashes available onchain through `blockhash`.
+pub const BLOCKHASH_WINDOW: u64 = 256;
+
+/// Number of recent beacon block roots kept by the EIP-4788 beacon roots contract, in slots.
+pub const BEACON_ROOTS_WINDOW: u64 = 8191;
+
+/// Version of the [`GuestInputBundle`] format, bumped on incompatible changes.
+pub const GUEST_INPUT_BUNDLE_VERSION: u32 = 3;
+
+/// Steel chain spec of the chain, the same one the guest executes the EVM input with.
+/// Devnets run the latest mainnet hardfork since genesis, their specs are built once per
+/// chain ID and kept for the lifetime of the process.
+pub fn chain_spec(chain: Chain) -> &'static EthChainSpec {
+    static DEVNET_CHAIN_SPECS: StdMutex<BTreeMap<u64, &'static EthChainSpec>> =
+        StdMutex::new(BTreeMap::new());
+    match chain {
+        Chain::Mainnet => &ETH_MAINNET_CHAIN_SPEC,
+        Chain::Sepolia => &ETH_SEPOLIA_CHAIN_SPEC,
+        Chain::Holesky => &ETH_HOLESKY_CHAIN_SPEC,
+        Chain::Devnet(chain_id) => {
+            let mut specs = DEVNET_CHAIN_SPECS
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            specs.entry(chain_id).or_insert_with(|| {
+                let latest_fork = *ETH_MAINNET_CHAIN_SPEC
+                    .forks
+                    .keys()
+                    .last()
+                    .expect("mainnet chain spec has hardforks");
+                Box::leak(Box::new(EthChainSpec::new_single(chain_id, latest_fork)))
+            })
+        }
+    }
+}
+
+/// Self-contained guest input, with the cert, payload and KZG proof hex encoded.
+/// It is persisted between the attempts of a proof request, and can be exported
+/// to execute or prove the guest offline with the `replay` binary.
+#[derive(Serialize, Deserialize)]
+pub struct GuestInputBundle {
+    pub version: u32,
+    /// Chain the EVM input was built for.
+    pub chain: Chain,
+    pub evm_input: EthEvmInput,
+    /// Hex encoded ABI `EigenDACert`.
+    pub eigenda_cert: String,
+    /// Hex encoded payload.
+    pub data: String,
+    /// Hex encoded compressed KZG proof.
+    pub kzg_proof: String,
+    pub cert_verifier_router_addr: Address,
+    pub polynomial_form: PolynomialForm,
+    pub anchor_block_number: u64,
+    pub anchor_block_hash: B256,
+}
+
+/// Settings a guest input is built with. A stored input is only reused by a service
+/// proving with the same ones, otherwise it's rebuilt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputSettings {
+    pub chain: Chain,
+    /// Whether the steel environment commits to a beacon block root.
+    pub beacon_committed: bool,
+}
+
+/// Why a stored guest input can't be reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMismatch {
+    /// The input was built for the given chain.
+    Chain(Chain),
+    /// The input has the other kind of environment commitment.
+    Commitment,
+}
+
+impl InputSettings {
+    /// Returns why an input built with the `stored` settings can't be reused with these ones,
+    /// `None` if it can.
+    pub fn mismatch(&self, stored: InputSettings) -> Option<InputMismatch> {
+        if stored.chain != self.chain {
+            Some(InputMismatch::Chain(stored.chain))
+        } else if stored.beacon_committed != self.beacon_committed {
+            Some(InputMismatch::Commitment)
+        } else {
+            None
+        }
+    }
+}
+
+impl GuestInput {
+    /// Settings the input was built with.
+    pub fn settings(&self) -> InputSettings {
+        InputSettings {
+            chain: self.chain,
+            beacon_committed: self.is_beacon_committed(),
+        }
+    }
+
+    /// Whether the steel environment commits to a beacon block root (EIP-4788)
+    /// instead of an execution block hash.
+    pub fn is_beacon_committed(&self) -> bool {
+        matches!(self.evm_input, EvmInput::Beacon(_))
+    }
+
+    /// Last block the steel environment commitment can be verified onchain at. For beacon
+    /// commitments slots are counted as blocks, so missed slots make it a bit earlier.
+    pub fn expiry_block(&self) -> u64 {
+        let window = if self.is_beacon_committed() {
+            BEACON_ROOTS_WINDOW
+        } else {
+            BLOCKHASH_WINDOW
+        };
+        self.anchor_block_number + window
+    }
+
+    /// Serializes the inputs as the guest stdin, the same way `ExecutorEnv::write` would.
+    pub fn to_stdin(&self) -> anyhow::Result<Vec<u8>> {
+        let words = [
+            to_vec(&self.evm_input)?,
+            to_vec(&self.eigenda_cert)?,
+            to_vec(&self.data)?,
+            to_vec(&self.kzg_proof)?,
+            to_vec(&self.cert_verifier_router_addr)?,
+            to_vec(&self.polynomial_form)?,
+            to_vec(&self.chain)?,
+        ]
+        .concat();
+        Ok(words.iter().flat_map(|word| word.to_le_bytes()).collect())
+    }
+
+    /// Encodes the input as a JSON [`GuestInputBundle`].
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string(&self.to_bundle()?)?)
+    }
+
+    /// Decodes an input encoded with [`GuestInput::to_json`].
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        Self::from_bundle(serde_json::from_str(json)?)
+    }
+
+    /// Exports the input as a [`GuestInputBundle`].
+    pub fn to_bundle(&self) -> anyhow::Result<GuestInputBundle> {
+        let mut kzg_proof = Vec::new();
+        self.kzg_proof.g1.serialize_compressed(&mut kzg_proof)?;
+        Ok(GuestInputBundle {
+            version: GUEST_INPUT_BUNDLE_VERSION,
+            chain: self.chain,
+            evm_input: self.evm_input.clone(),
+            eigenda_cert: hex::encode(self.eigenda_cert.to_abi_encoded()?),
+            data: hex::encode(&self.data),
+            kzg_proof: hex::encode(kzg_proof),
+            cert_verifier_router_addr: self.cert_verifier_router_addr,
+            polynomial_form: self.polynomial_form.clone(),
+            anchor_block_number: self.anchor_block_number,
+            anchor_block_hash: self.anchor_block_hash,
+        })
+    }
+
+    /// Decodes the bundle, checking its format version.
+    pub fn from_bundle(bundle: GuestInputBundle) -> anyhow::Result<Self> {
+        if bundle.version != GUEST_INPUT_BUNDLE_VERSION {
+            anyhow::bail!(
+                "Unsupported guest input bundle version {}, expected {}",
+                bundle.version,
+                GUEST_INPUT_BUNDLE_VERSION
+            );
+        }
+        let kzg_proof = G1Affine::deserialize_compressed(&hex::decode(bundle.kzg_proof)?[..])?;
+        Ok(Self {
+            evm_input: bundle.evm_input,
+            eigenda_cert: decode_eigenda_cert(&bundle.eigenda_cert)?,
+            data: hex::decode(bundle.data)?,
+            kzg_proof: SerializableG1 { g1: kzg_proof },
+            cert_verifier_router_addr: bundle.cert_verifier_router_addr,
+            polynomial_form: bundle.polynomial_form,
+            chain: bundle.chain,
+            anchor_block_number: bundle.anchor_block_number,
+            anchor_block_hash: bundle.anchor_block_hash,
+        })
+    }
+}
+
+/// Guest inputs in the order they are written to its stdin by [`GuestInput::to_stdin`].
+type GuestStdin = (
+    EthEvmInput,
+    EigenDACert,
+    Vec<u8>,
+    SerializableG1,
+    Address,
+    PolynomialForm,
+    Chain,
+);
+
+/// Reads back the words of a guest stdin written as little endian bytes.
+fn stdin_words(stdin: &[u8]) -> anyhow::Result<Vec<u32>> {
+    let (words, rest) = stdin.as_chunks::<4>();
+    if !rest.is_empty() {
+        anyhow::bail!("Guest stdin is not made of 32-bit words");
+    }
+    Ok(words.iter().map(|word| u32::from_le_bytes(*word)).collect())
+}
+
+/// Computes the output the guest commits for the given stdin, without running it: the cert
+/// validity and the KZG proof are not checked. Used to give fake receipts the journal of the
+/// real ones, so their proofs can be decoded and aggregated.
+pub fn guest_output(stdin: &[u8]) -> anyhow::Result<Output> {
+    let (evm_input, _, data, kzg_proof, _, _, chain): GuestStdin =
+        from_slice(&stdin_words(stdin)?)?;
+    let env = evm_input.into_env(chain_spec(chain));
+    let mut proof = Vec::new();
+    kzg_proof.g1.serialize_compressed(&mut proof)?;
+    Ok(Output {
+        hash: keccak256(&data).to_vec(),
+        env_commitment: SolValue::abi_encode(env.commitment()),
+        proof,
+        chain_id: chain.chain_id(),
+    })
+}
+
+/// Inputs of the aggregation guest, written to its stdin in the order it reads them.
+pub struct AggregationInput {
+    /// Image ID of the guest that proved the aggregated blobs.
+    pub image_id: [u32; 8],
+    /// Journals of the blob receipts, in the order they are aggregated.
+    pub journals: Vec<Vec<u8>>,
+}
+
+impl AggregationInput {
+    /// Serializes the inputs as the aggregation guest stdin, the same way `ExecutorEnv::write` would.
+    pub fn to_stdin(&self) -> anyhow::Result<Vec<u8>> {
+        let words = [to_vec(&self.image_id)?, to_vec(&self.journals)?].concat();
+        Ok(words.iter().flat_map(|word| word.to_le_bytes()).collect())
+    }
+
+    /// Decodes the inputs from the aggregation guest stdin, the inverse of `to_stdin`.
+    pub fn from_stdin(stdin: &[u8]) -> anyhow::Result<Self> {
+        let (image_id, journals) = from_slice(&stdin_words(stdin)?)?;
+        Ok(Self { image_id, journals })
+    }
+
+    /// Computes the output the aggregation guest commits for these inputs, without verifying
+    /// the blob receipts. Fails like the guest if a journal is not a blob guest output.
+    pub fn output(&self) -> anyhow::Result<AggregatedOutput> {
+        if self.journals.is_empty() {
+            anyhow::bail!("No blob proofs to aggregate");
+        }
+        let blobs = self
+            .journals
+            .iter()
+            .map(|journal| {
+                let output = Output::abi_decode(journal)
+                    .map_err(|e| anyhow::anyhow!("Invalid blob guest journal: {}", e))?;
+                Ok(AggregatedBlob {
+                    hash: output.hash,
+                    env_commitment: output.env_commitment,
+                    chain_id: output.chain_id,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(AggregatedOutput {
+            image_id: Digest::from(self.image_id).as_bytes().to_vec(),
+            blobs,
+        })
+    }
+}
+
+fn log_check_da_cert(cert_verifier_router_addr: Address, returns: u8) {
+    tracing::info!(
+        "Call {} Function on {:#} returns: {}",
+        IVerifyBlob::checkDACertCall::SIGNATURE,
+        cert_verifier_router_addr,
+        returns
+    );
+}
+
+/// Builds the guest input: preflights the `checkDACert` call with risc0 steel
+/// and computes the KZG proof of the blob against the cert commitment.
+/// If a beacon API endpoint is given, the steel environment commits to the beacon block root
+/// of the anchor block (EIP-4788) instead of its block hash, which can be verified on chain
+/// for much longer than the 256 blocks of the `blockhash` window.
+pub async fn build_guest_input(
     eigenda_cert: EigenDACert,
     srs: &SRS,
     data: Vec<u8>,
     rpc_url: Url,
     cert_verifier_router_addr: Address,
     payload_form: PayloadForm,
-) -> anyhow::Result<ProveInfo> {
+    chain: Chain,
+    anchor_block: u64,
+    beacon_api_url: Option<Url>,
+) -> anyhow::Result<GuestInput> {
     let call = IVerifyBlob::checkDACertCall {
         eigendacert: eigenda_cert.to_abi_encoded()?.into(),
     };
 
-    // Create an EVM environment from an RPC endpoint defaulting to the latest block.
-    let mut env = EthEvmEnv::builder()
+    // Create an EVM environment from an RPC endpoint, anchored to the given block.
+    let builder = EthEvmEnv::builder()
         .rpc(rpc_url.clone())
-        .chain_spec(&ETH_HOLESKY_CHAIN_SPEC)
-        .build()
-        .await?;
+        .block_number(anchor_block)
+        .chain_spec(chain_spec(chain));
 
     // Preflight the call to prepare the input that is required to execute the function in
     // the guest without RPC access. It also returns the result of the call.
     // Risc0 steel creates an ethereum VM using revm, where it simulates the call to checkDACert.
     // So we need to make this preflight call to populate the VM environment with the current state of the chain
-    let mut contract = Contract::preflight(cert_verifier_router_addr, &mut env);
-    let returns = contract.call_builder(&call).call().await?;
-    tracing::info!(
-        "Call {} Function on {:#} returns: {}",
-        IVerifyBlob::checkDACertCall::SIGNATURE,
-        cert_verifier_router_addr,
-        returns
-    );
+    // The environments committing to a block hash or to a beacon root have different types,
+    // so each one is preflighted separately.
+    let (anchor_block_number, anchor_block_hash, evm_input) = match beacon_api_url {
+        None => {
+            let mut env = builder.build().await?;
+            let mut contract = Contract::preflight(cert_verifier_router_addr, &mut env);
+            let returns = contract.call_builder(&call).call().await?;
+            log_check_da_cert(cert_verifier_router_addr, returns);
 
-    // Finally, construct the input from the environment.
-    let input = env.into_input().await?;
+            // Finally, construct the input from the environment.
+            let anchor_block_number = env.header().inner().number();
+            let anchor_block_hash = env.header().seal();
+            (
+                anchor_block_number,
+                anchor_block_hash,
+                env.into_input().await?,
+            )
+        }
+        Some(beacon_api_url) => {
+            let mut env = builder.beacon_api(beacon_api_url).build().await?;
+            let mut contract = Contract::preflight(cert_verifier_router_addr, &mut env);
+            let returns = contract.call_builder(&call).call().await?;
+            log_check_da_cert(cert_verifier_router_addr, returns);
+
+            let anchor_block_number = env.header().inner().number();
+            let anchor_block_hash = env.header().seal();
+            (
+                anchor_block_number,
+                anchor_block_hash,
+                env.into_input().await?,
+            )
+        }
+    };
 
     let payload = Payload::new(data.clone());
     let encoded_data = payload.to_blob(payload_form)?.serialize();
@@ -77,33 +440,54 @@ pub async fn run_guest(
     // Compute the proof that the commitment corresponds to the given blob
     let proof = kzg.compute_proof(&poly_eval, &evaluation_challenge, srs)?;
 
-    let serializable_proof = SerializableG1 { g1: proof };
-
     let polynomial_form = match payload_form {
         PayloadForm::Coeff => PolynomialForm::Coeff,
         PayloadForm::Eval => PolynomialForm::Eval,
     };
 
-    tracing::info!("Running the guest with the constructed input...");
-    let session_info = tokio::task::spawn_blocking(move || -> anyhow::Result<_> {
-        let env = ExecutorEnv::builder()
-            .write(&input)?
-            .write(&eigenda_cert)?
-            .write(&data)?
-            .write(&serializable_proof)?
-            .write(&cert_verifier_router_addr)?
-            .write(&polynomial_form)?
-            .build()?;
-        let exec = default_prover();
-        exec.prove_with_ctx(
-            env,
-            &VerifierContext::default(),
-            GUEST_ELF,
-            &ProverOpts::groth16(),
-        )
-        .context("failed to run executor")
+    Ok(GuestInput {
+        evm_input,
+        eigenda_cert,
+        data,
+        kzg_proof: SerializableG1 { g1: proof },
+        cert_verifier_router_addr,
+        polynomial_form,
+        chain,
+        anchor_block_number,
+        anchor_block_hash,
     })
-    .await??;
+}
+
+/// Runs the guest with the given stdin (see `GuestInput::to_stdin`) in the executor, without
+/// proving it. Fails with the guest panic message if any of its assertions fails.
+pub fn execute_guest(stdin: &[u8]) -> anyhow::Result<Session> {
+    let env = ExecutorEnv::builder().write_slice(stdin).build()?;
+    let mut executor = ExecutorImpl::from_elf(env, GUEST_ELF)?;
+    // The segments are only needed to prove the execution, don't keep them in memory
+    executor.run_with_callback(|_| Ok(Box::new(NullSegmentRef)))
+}
 
-    Ok(session_info)
+/// Runs the aggregation guest with the given stdin (see `AggregationInput::to_stdin`) in the
+/// executor, without proving it. The blob receipts are added as assumptions, but not verified.
+pub fn execute_aggregator(stdin: &[u8], receipts: Vec<Receipt>) -> anyhow::Result<Session> {
+    let mut env = ExecutorEnv::builder();
+    for receipt in receipts {
+        env.add_assumption(receipt);
+    }
+    let env = env.write_slice(stdin).build()?;
+    let mut executor = ExecutorImpl::from_elf(env, AGGREGATOR_ELF)?;
+    executor.run_with_callback(|_| Ok(Box::new(NullSegmentRef)))
+}
+
+/// Proves the guest input with the given prover backend, into the given kind of receipt.
+pub async fn run_guest(
+    prover: &dyn ProverBackend,
+    input: GuestInput,
+    receipt_kind: ReceiptKind,
+) -> anyhow::Result<ProveOutput> {
+    tracing::info!(
+        "Running the guest with the constructed input on the {} prover...",
+        prover.kind()
+    );
+    prover.prove(input.to_stdin()?, receipt_kind).await
 }
@@ -0,0 +1,253 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use rust_eigenda_v2_client::relay_client::RelayClient;
+use serde::Serialize;
+use sqlx::{Pool, Postgres};
+use tokio::sync::Mutex;
+use url::Url;
+
+use crate::{
+    chain::{BeaconApiClient, EthRpcClient},
+    worker::ProofWorker,
+};
+
+/// Maximum time between two iterations of the idle worker loop before it is considered wedged.
+pub const WORKER_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Maximum time a single proof generation can take before the worker is considered wedged.
+pub const WORKER_JOB_TIMEOUT: Duration = Duration::from_secs(2 * 60 * 60);
+
+/// Maximum time each readiness check can take.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Heartbeat of the proof generation loop, used to detect if it is wedged.
+#[derive(Default)]
+pub struct WorkerHeartbeat {
+    /// Unix time of the last loop iteration.
+    last_beat: AtomicU64,
+    /// Unix time the current proof generation started, 0 if the worker is idle.
+    job_started: AtomicU64,
+}
+
+impl WorkerHeartbeat {
+    /// Records an iteration of the worker loop.
+    pub fn beat(&self) {
+        self.last_beat.store(unix_now(), Ordering::Relaxed);
+    }
+
+    /// Records that the worker started proving a request.
+    pub fn job_started(&self) {
+        self.beat();
+        self.job_started.store(unix_now(), Ordering::Relaxed);
+    }
+
+    /// Records that the worker finished proving a request.
+    pub fn job_finished(&self) {
+        self.job_started.store(0, Ordering::Relaxed);
+        self.beat();
+    }
+
+    fn check(&self) -> CheckResult {
+        let now = unix_now();
+        let job_started = self.job_started.load(Ordering::Relaxed);
+        if job_started != 0 {
+            let elapsed = now.saturating_sub(job_started);
+            return CheckResult {
+                ok: elapsed <= WORKER_JOB_TIMEOUT.as_secs(),
+                detail: format!("proving for {}s", elapsed),
+            };
+        }
+        let last_beat = self.last_beat.load(Ordering::Relaxed);
+        if last_beat == 0 {
+            return CheckResult::failed("worker loop not started");
+        }
+        let elapsed = now.saturating_sub(last_beat);
+        CheckResult {
+            ok: elapsed <= WORKER_IDLE_TIMEOUT.as_secs(),
+            detail: format!("idle, last loop iteration {}s ago", elapsed),
+        }
+    }
+}
+
+/// Result of a single health check.
+#[derive(Debug, Serialize)]
+pub struct CheckResult {
+    pub ok: bool,
+    pub detail: String,
+    /// Whether the probe fails if this check fails, otherwise the check is only reported.
+    pub required: bool,
+}
+
+impl CheckResult {
+    fn passed(detail: impl Into<String>) -> Self {
+        Self {
+            ok: true,
+            detail: detail.into(),
+            required: true,
+        }
+    }
+
+    fn failed(detail: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            detail: detail.into(),
+            required: true,
+        }
+    }
+
+    fn required(self, required: bool) -> Self {
+        Self { required, ..self }
+    }
+
+    fn from_result(result: anyhow::Result<String>) -> Self {
+        match result {
+            Ok(detail) => Self::passed(detail),
+            Err(e) => Self::failed(e.to_string()),
+        }
+    }
+}
+
+/// Result of all the checks of a probe, served as JSON.
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub ok: bool,
+    pub checks: BTreeMap<&'static str, CheckResult>,
+}
+
+impl HealthReport {
+    fn new(checks: BTreeMap<&'static str, CheckResult>) -> Self {
+        Self {
+            ok: checks.values().all(|check| check.ok || !check.required),
+            checks,
+        }
+    }
+}
+
+/// Liveness and readiness checks of the proving service, served on `/healthz` and `/readyz`.
+pub struct HealthChecker {
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    eth_rpc: EthRpcClient,
+    beacon_api: Option<BeaconApiClient>,
+    disperser_rpc: String,
+    http: reqwest::Client,
+    worker: Arc<ProofWorker>,
+    relays_required: bool,
+}
+
+impl HealthChecker {
+    /// Creates the checker. The relays check only fails the readiness probe if
+    /// `relays_required`, otherwise it is only reported, since the proof requests
+    /// uploading their payload don't need the relays.
+    pub fn new(
+        db_pool: Arc<Mutex<Pool<Postgres>>>,
+        rpc_url: Url,
+        beacon_api_url: Option<Url>,
+        disperser_rpc: String,
+        worker: Arc<ProofWorker>,
+        relays_required: bool,
+    ) -> Self {
+        Self {
+            db_pool,
+            eth_rpc: EthRpcClient::new(rpc_url),
+            beacon_api: beacon_api_url.map(BeaconApiClient::new),
+            disperser_rpc,
+            http: reqwest::Client::new(),
+            worker,
+            relays_required,
+        }
+    }
+
+    /// The process is alive and the worker loop is not wedged.
+    pub fn liveness(&self) -> HealthReport {
+        HealthReport::new(BTreeMap::from([(
+            "worker",
+            self.worker.heartbeat().check(),
+        )]))
+    }
+
+    /// The dependencies needed to accept and prove requests are available.
+    pub async fn readiness(&self) -> HealthReport {
+        let (database, eth_rpc, beacon_api, disperser, relays) = tokio::join!(
+            with_timeout(self.check_database()),
+            with_timeout(self.check_eth_rpc()),
+            with_timeout(self.check_beacon_api()),
+            with_timeout(self.check_disperser()),
+            with_timeout(self.check_relays()),
+        );
+        let srs_points = self.worker.srs_points_loaded();
+        let srs = if srs_points > 0 {
+            CheckResult::passed(format!("{} points loaded", srs_points))
+        } else {
+            CheckResult::failed("SRS not loaded")
+        };
+
+        HealthReport::new(BTreeMap::from([
+            ("database", database),
+            ("eth_rpc", eth_rpc),
+            ("beacon_api", beacon_api),
+            ("disperser", disperser),
+            ("relays", relays.required(self.relays_required)),
+            ("srs", srs),
+        ]))
+    }
+
+    async fn check_database(&self) -> anyhow::Result<String> {
+        let db_lock = self.db_pool.lock().await;
+        sqlx::query("SELECT 1").execute(&*db_lock).await?;
+        Ok("reachable".to_string())
+    }
+
+    async fn check_eth_rpc(&self) -> anyhow::Result<String> {
+        let block_number = self.eth_rpc.block_number().await?;
+        Ok(format!("latest block {}", block_number))
+    }
+
+    async fn check_beacon_api(&self) -> anyhow::Result<String> {
+        let Some(beacon_api) = &self.beacon_api else {
+            return Ok("not configured".to_string());
+        };
+        let slot = beacon_api.head_slot().await?;
+        Ok(format!("head slot {}", slot))
+    }
+
+    // The disperser client has no call that succeeds without a dispersed blob, so the disperser
+    // endpoint is requested directly: any HTTP response, even an error one, means it's reachable
+    async fn check_disperser(&self) -> anyhow::Result<String> {
+        let response = self.http.get(&self.disperser_rpc).send().await?;
+        Ok(format!("reachable, HTTP {}", response.status().as_u16()))
+    }
+
+    // A separate relay client is connected, so the probe doesn't initialise the payload
+    // retriever of the worker (and load its SRS points) as a side effect
+    async fn check_relays(&self) -> anyhow::Result<String> {
+        RelayClient::new(self.worker.relay_client_config()).await?;
+        if self.worker.relays_connected() {
+            Ok("reachable, payload retriever initialised".to_string())
+        } else {
+            Ok("reachable".to_string())
+        }
+    }
+}
+
+async fn with_timeout(
+    check: impl std::future::Future<Output = anyhow::Result<String>>,
+) -> CheckResult {
+    match tokio::time::timeout(CHECK_TIMEOUT, check).await {
+        Ok(result) => CheckResult::from_result(result),
+        Err(_) => CheckResult::failed(format!("timed out after {}s", CHECK_TIMEOUT.as_secs())),
+    }
+}
@@ -0,0 +1,54 @@
+use alloy_primitives::Address;
+use anyhow::Result;
+use client::types::{AnchorPolicy, ChainInfo, ServiceInfo, SrsInfo};
+use common::chain::Chain;
+use common::polynomial_form::PolynomialForm;
+use methods::{AGGREGATOR_ELF, GUEST_ELF};
+use risc0_zkvm::{compute_image_id, sha::Digestible, Groth16ReceiptVerifierParameters};
+
+/// Builds the description of the configuration the proofs are generated with,
+/// returned by the `get_service_info` method.
+#[allow(clippy::too_many_arguments)]
+pub fn build_service_info(
+    chain: Chain,
+    cert_verifier_router_addr: Address,
+    payload_form: &PolynomialForm,
+    anchor_policy: AnchorPolicy,
+    beacon_commitment: bool,
+    blob_version: u16,
+    srs: SrsInfo,
+    dev_mode: bool,
+) -> Result<ServiceInfo> {
+    let image_id = compute_image_id(GUEST_ELF)?;
+    let aggregator_image_id = compute_image_id(AGGREGATOR_ELF)?;
+    let verifier_parameters = Groth16ReceiptVerifierParameters::default().digest();
+    let verifier_selector = verifier_parameters
+        .as_bytes()
+        .get(..4)
+        .ok_or(anyhow::anyhow!("verifier parameters too short"))?;
+
+    let payload_form = match payload_form {
+        PolynomialForm::Coeff => "coeff",
+        PolynomialForm::Eval => "eval",
+    };
+
+    Ok(ServiceInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        image_id: hex::encode(image_id.as_bytes()),
+        aggregator_image_id: hex::encode(aggregator_image_id.as_bytes()),
+        risc0_version: risc0_zkvm::VERSION.to_string(),
+        verifier_parameters: hex::encode(verifier_parameters.as_bytes()),
+        verifier_selector: hex::encode(verifier_selector),
+        chain: ChainInfo {
+            name: chain.name().to_string(),
+            chain_id: chain.chain_id(),
+        },
+        cert_verifier_router_addr: cert_verifier_router_addr.to_string(),
+        payload_form: payload_form.to_string(),
+        anchor_policy,
+        env_commitment: if beacon_commitment { "beacon" } else { "block" }.to_string(),
+        blob_version,
+        srs,
+        dev_mode,
+    })
+}
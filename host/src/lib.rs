@@ -1,2 +1,19 @@
+// The OpenAPI document of the REST API is a single `json!` invocation
+#![recursion_limit = "256"]
+
+pub mod admin;
+pub mod chain;
 pub mod db;
+pub mod freshness;
+pub mod grpc;
 pub mod guest_caller;
+pub mod health;
+pub mod info;
+pub mod metrics;
+pub mod notifier;
+pub mod prover;
+pub mod reorg;
+pub mod rest;
+pub mod rpc;
+pub mod service;
+pub mod worker;
@@ -12,72 +12,55 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{sync::Arc, time::Duration};
+use std::sync::Arc;
 
 use alloy_primitives::Address;
 use anyhow::Result;
-use clap::Parser;
+use clap::{builder::BoolishValueParser, Parser};
+use client::types::{AnchorPolicy, SrsInfo};
+use common::chain::Chain;
 use common::polynomial_form::PolynomialForm;
-use ethabi::Token;
-use host::db::{
-    mark_blob_proof_request_failed, proof_request_exists, retrieve_blob_id_proof,
-    retrieve_next_pending_proof, store_blob_proof, store_blob_proof_request,
-};
-use jsonrpc_core::{ErrorCode, IoHandler, Params};
+use host::admin::WorkerControl;
+use host::chain::EthRpcClient;
+use host::freshness::FreshnessMonitor;
+use host::health::HealthChecker;
+use host::info::build_service_info;
+use host::metrics::{run_metrics_server, PROVER_BACKEND_INFO};
+use host::notifier::ProofNotifier;
+use host::prover::{build_prover_backend, ProverBackendKind};
+use host::reorg::ReorgMonitor;
+use host::rpc::{build_admin_rpc_handler, build_rpc_handler};
+use host::service::ProvingService;
+use host::worker::{ProofWorker, RelayConfig, SRS_ORDER, SRS_PATH, SRS_POINTS_TO_LOAD};
 use jsonrpc_http_server::ServerBuilder;
-use methods::GUEST_ELF;
-use risc0_zkvm::compute_image_id;
 use rust_eigenda_v2_client::{
-    core::BlobKey,
     payload_disperser::{PayloadDisperser, PayloadDisperserConfig},
-    relay_client::{RelayClient, RelayClientConfig},
-    relay_payload_retriever::{RelayPayloadRetriever, RelayPayloadRetrieverConfig, SRSConfig},
     rust_eigenda_signers::signers::private_key::Signer,
     utils::SecretUrl,
 };
-use rust_eigenda_v2_common::{EigenDACert, Payload, PayloadForm};
+use rust_eigenda_v2_common::PayloadForm;
 use secrecy::{ExposeSecret, Secret};
-use serde::Deserialize;
 use sqlx::PgPool;
-use tiny_http::{Header, Response, Server as MetricsServer};
 use tokio::{sync::Mutex, task::JoinHandle};
 use tracing_subscriber::EnvFilter;
 
 use rust_kzg_bn254_prover::srs::SRS;
 use url::Url;
 
-use prometheus::{
-    self, register_histogram_vec, register_int_counter, Encoder, HistogramVec, IntCounter,
-};
-
-// Prometheus metrics
-lazy_static::lazy_static! {
-static ref PROOF_GEN_REQ_COUNTER: IntCounter =
-    register_int_counter!("proof_requests", "Number of proof generation requests received").unwrap();
-
-    static ref PROOF_GEN_SUCCESS_COUNTER: IntCounter =
-    register_int_counter!("proof_generations", "Number of successful proofs generated").unwrap();
-
-    static ref PROOF_GEN_FAILURE_COUNTER: IntCounter =
-    register_int_counter!("proof_generation_failures", "Number of failed proof generations").unwrap();
-
-    static ref PROOF_RET_REQ_COUNTER: IntCounter =
-    register_int_counter!("proof_retrievals", "Number of proof retrieval requests received").unwrap();
-
-    static ref PROOF_GEN_TIME_HISTOGRAM: HistogramVec =
-        register_histogram_vec!(
-            "proof_generation_seconds",
-            "Time taken to generate a proof in seconds",
-            &["blob_id"]
-        ).unwrap();
-    }
-
 #[derive(Parser, Debug)]
 #[command(about, long_about = None)]
 struct Args {
     /// URL of the RPC endpoint
     #[arg(short, long, env = "RPC_URL")]
     rpc_url: Url,
+    /// Chain the EigenDA certs are verified against (mainnet, sepolia, holesky, devnet or
+    /// devnet:<chain_id>), the RPC endpoint must serve it
+    #[arg(long, env = "CHAIN", default_value_t = Chain::Holesky)]
+    chain: Chain,
+    /// URL of a beacon node API of the chain. If set, the proofs commit to a beacon block root
+    /// (EIP-4788) instead of a block hash, so they can be verified on chain for much longer
+    #[arg(long, env = "BEACON_API_URL")]
+    beacon_api_url: Option<Url>,
     /// Private key used to get inclusion data from the disperser
     #[arg(short, long, env = "DISPERSER_PRIVATE_KEY")]
     disperser_private_key: Secret<String>,
@@ -99,6 +82,9 @@ struct Args {
     /// Keys of the relay client
     #[arg(short, long, env = "RELAY_CLIENT_KEYS", value_delimiter = ',')]
     relay_client_keys: Vec<u32>,
+    /// Fails the readiness probe when the relays can't be connected, instead of only reporting it
+    #[arg(long, env = "RELAYS_REQUIRED_FOR_READINESS", value_parser = BoolishValueParser::new())]
+    relays_required_for_readiness: bool,
     /// URL where this proving service should run
     #[arg(short, long, env = "PROVING_SERVICE_URL")]
     proving_service_url: String,
@@ -114,17 +100,42 @@ struct Args {
     /// Address of the Eigen Operator State Retriever
     #[arg(short, long, env = "OPERATOR_STATE_RETRIEVER_ADDR")]
     operator_state_retriever_addr: String,
-}
-
-const SRS_ORDER: u32 = 268435456;
-const SRS_POINTS_TO_LOAD: u32 = 1024 * 1024 * 2 / 32;
-
-// The JSON RPC Specification defines for the Server error (Reserved for implementation-defined server-errors) the range of codes -32000 to -32099
-const PROOF_NOT_FOUND_ERROR: i64 = -32001;
-
-#[derive(Deserialize)]
-struct GenerateProofParams {
-    blob_id: String,
+    /// URL where the REST API should run, disabled if not set
+    #[arg(long, env = "REST_URL")]
+    rest_url: Option<String>,
+    /// Address where the gRPC API should run, disabled if not set
+    #[arg(long, env = "GRPC_URL")]
+    grpc_url: Option<String>,
+    /// URL where the admin JSON RPC methods should run, disabled if not set
+    #[arg(long, env = "ADMIN_URL")]
+    admin_url: Option<String>,
+    /// Backend the proofs are generated with
+    #[arg(long, value_enum, env = "PROVER_BACKEND", default_value_t = ProverBackendKind::local())]
+    prover_backend: ProverBackendKind,
+    /// URL of the remote prover, required by the remote prover backend
+    #[arg(long, env = "REMOTE_PROVER_URL")]
+    remote_prover_url: Option<Url>,
+    /// Enables dev mode, allowing the dev and mock prover backends, which generate fake proofs
+    #[arg(long, env = "PROVING_SERVICE_DEV_MODE", value_parser = BoolishValueParser::new())]
+    dev_mode: bool,
+    /// Maximum number of blocks the anchor block of a stored guest input can be behind the
+    /// latest block for it to be reused when retrying its proof request, 0 to never reuse them
+    #[arg(long, env = "MAX_INPUT_AGE_BLOCKS", default_value_t = 256)]
+    max_input_age_blocks: u64,
+    /// Block the cert validity is proven at, for the proof requests that don't choose one:
+    /// latest, latest-<confirmations>, <block number> or reference (the cert reference block)
+    #[arg(long, env = "ANCHOR_POLICY", default_value_t = AnchorPolicy::Latest)]
+    anchor_policy: AnchorPolicy,
+    /// Number of blocks before their expiry block the proofs are considered expiring
+    #[arg(long, env = "EXPIRY_MARGIN_BLOCKS", default_value_t = 32)]
+    expiry_margin_blocks: u64,
+    /// Queues the unconsumed proofs that are expiring to prove them again anchored to a newer block
+    #[arg(long, env = "AUTO_REPROVE", value_parser = BoolishValueParser::new())]
+    auto_reprove: bool,
+    /// Number of blocks behind the latest one the anchor blocks of the proofs are re-checked for
+    /// reorgs, 0 to disable the reorg detection
+    #[arg(long, env = "REORG_CHECK_DEPTH", default_value_t = 128)]
+    reorg_check_depth: u64,
 }
 
 async fn flatten(handle: JoinHandle<Result<()>>) -> Result<()> {
@@ -135,77 +146,6 @@ async fn flatten(handle: JoinHandle<Result<()>>) -> Result<()> {
     }
 }
 
-// Receives a blob ID with the arguments needed to generate its proof.
-// Returns the proof encoded as and ethabi Tuple.
-async fn generate_proof(
-    blob_id: String,
-    payload_disperser: Arc<PayloadDisperser>,
-    retriever: Arc<Mutex<RelayPayloadRetriever>>,
-    srs: &SRS,
-    rpc_url: Url,
-    cert_verifier_router_addr: Address,
-    payload_form: PayloadForm,
-) -> Result<Vec<u8>> {
-    let eigenda_cert: EigenDACert;
-    loop {
-        let blob_key = BlobKey::from_hex(&blob_id)?;
-        let opt_eigenda_cert = payload_disperser.get_cert(&blob_key).await?;
-        if let Some(opt_eigenda_cert) = opt_eigenda_cert {
-            eigenda_cert = opt_eigenda_cert;
-            break;
-        }
-        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-    }
-
-    // Raw bytes dispersed by zksync sequencer to EigenDA
-    let payload: Payload = retriever
-        .lock()
-        .await
-        .get_payload(eigenda_cert.clone())
-        .await?;
-
-    let blob_data = payload.serialize();
-
-    let result = host::guest_caller::run_guest(
-        eigenda_cert.clone(),
-        srs,
-        blob_data,
-        rpc_url,
-        cert_verifier_router_addr,
-        payload_form,
-    )
-    .await?;
-
-    let image_id = compute_image_id(GUEST_ELF)?;
-    let image_id: risc0_zkvm::sha::Digest = image_id;
-    let image_id = image_id.as_bytes().to_vec();
-
-    let block_proof = match result.receipt.inner.groth16() {
-        Ok(inner) => {
-            // The SELECTOR is used to perform an extra check inside the groth16 verifier contract.
-            let mut selector = hex::encode(
-                inner
-                    .verifier_parameters
-                    .as_bytes()
-                    .get(..4)
-                    .ok_or(anyhow::anyhow!("verifier parameters too short"))?,
-            );
-            let seal = hex::encode(inner.clone().seal);
-            selector.push_str(&seal);
-            hex::decode(selector)?
-        }
-        Err(_) => vec![0u8; 4],
-    };
-
-    let proof = ethabi::encode(&[Token::Tuple(vec![
-        Token::Bytes(block_proof),
-        Token::FixedBytes(image_id),
-        Token::Bytes(result.receipt.journal.bytes),
-    ])]);
-
-    Ok(proof)
-}
-
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt()
@@ -216,13 +156,33 @@ async fn main() -> Result<()> {
     let proving_service_url = args.proving_service_url.clone();
     let database_url = args.database_url.clone();
     let metrics_url = args.metrics_url.clone();
+    let rest_url = args.rest_url.clone();
+    let grpc_url = args.grpc_url.clone();
+    let admin_url = args.admin_url.clone();
+
+    let rpc_chain_id = EthRpcClient::new(args.rpc_url.clone())
+        .chain_id()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to retrieve the RPC chain ID: {}", e))?;
+    if rpc_chain_id != args.chain.chain_id() {
+        anyhow::bail!(
+            "RPC endpoint serves chain ID {}, but the {} chain ID is {}",
+            rpc_chain_id,
+            args.chain,
+            args.chain.chain_id()
+        );
+    }
+    tracing::info!("Verifying EigenDA certs against {}", args.chain);
 
     let db_pool = PgPool::connect(&database_url)
         .await
         .map_err(|e| anyhow::anyhow!("Failed to connect to database: {}", e))?;
     let db_pool = Arc::new(Mutex::new(db_pool));
+    host::db::migrate(db_pool.clone())
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to migrate database: {}", e))?;
 
-    let srs = SRS::new("resources/g1.point", SRS_ORDER, SRS_POINTS_TO_LOAD)?;
+    let srs = SRS::new(SRS_PATH, SRS_ORDER, SRS_POINTS_TO_LOAD)?;
 
     let payload_form = match args.payload_form {
         PolynomialForm::Eval => PayloadForm::Eval,
@@ -234,7 +194,7 @@ async fn main() -> Result<()> {
         blob_version: args.blob_version,
         cert_verifier_router_address: args.eigenda_cert_verifier_router_addr.to_string(),
         eth_rpc_url: SecretUrl::new(args.rpc_url.clone()),
-        disperser_rpc: args.disperser_rpc,
+        disperser_rpc: args.disperser_rpc.clone(),
         use_secure_grpc_flag: true,
         registry_coordinator_addr: args.registry_coordinator_addr,
         operator_state_retriever_addr: args.operator_state_retriever_addr,
@@ -251,200 +211,106 @@ async fn main() -> Result<()> {
             .map_err(|e| anyhow::anyhow!("Eigen client Error: {:?}", e))?,
     );
 
-    let db_pool_clone = db_pool.clone();
-    let payload_disperser_clone = payload_disperser.clone();
-    let proof_gen_thread: JoinHandle<Result<()>> = tokio::spawn(async move {
-        let payload_form = match args.payload_form {
-            PolynomialForm::Eval => PayloadForm::Eval,
-            PolynomialForm::Coeff => PayloadForm::Coeff,
-        };
-
-        let retriever_config = RelayPayloadRetrieverConfig {
-            payload_form,
-            retrieval_timeout_secs: Duration::from_secs(60),
-        };
-        let srs_config = SRSConfig {
-            source_path: "resources/g1.point".to_string(),
+    let service_info = build_service_info(
+        args.chain,
+        args.eigenda_cert_verifier_router_addr,
+        &args.payload_form,
+        args.anchor_policy,
+        args.beacon_api_url.is_some(),
+        args.blob_version,
+        SrsInfo {
+            path: SRS_PATH.to_string(),
             order: SRS_ORDER,
-            points_to_load: SRS_POINTS_TO_LOAD,
-        };
-
-        let relay_client_config = RelayClientConfig {
-            max_grpc_message_size: SRS_ORDER as usize,
-            relay_clients_keys: args.relay_client_keys,
+            points_loaded: SRS_POINTS_TO_LOAD,
+        },
+        args.dev_mode,
+    )?;
+    tracing::info!("Proving with guest image ID {}", service_info.image_id);
+    let notifier = ProofNotifier::new();
+    let control = Arc::new(WorkerControl::load(db_pool.clone()).await?);
+    let prover = build_prover_backend(
+        args.prover_backend,
+        args.remote_prover_url.clone(),
+        args.dev_mode,
+    )?;
+    tracing::info!("Proving with the {} prover backend", prover.kind());
+    PROVER_BACKEND_INFO
+        .with_label_values(&[&prover.kind().to_string()])
+        .set(1);
+    let worker = Arc::new(ProofWorker::new(
+        db_pool.clone(),
+        payload_disperser.clone(),
+        RelayConfig {
+            relay_client_keys: args.relay_client_keys,
             relay_registry_address: args.eigenda_relay_registry_addr,
-            eth_rpc_url: SecretUrl::new(args.rpc_url.clone()),
+        },
+        srs,
+        args.rpc_url.clone(),
+        args.eigenda_cert_verifier_router_addr,
+        payload_form,
+        args.chain,
+        args.beacon_api_url.clone(),
+        notifier.clone(),
+        control.clone(),
+        prover,
+        args.max_input_age_blocks,
+        args.expiry_margin_blocks,
+    ));
+    let service = Arc::new(ProvingService::new(
+        db_pool.clone(),
+        payload_disperser.clone(),
+        service_info,
+        notifier.clone(),
+        worker.clone(),
+        args.expiry_margin_blocks,
+    ));
+    let health = Arc::new(HealthChecker::new(
+        db_pool.clone(),
+        args.rpc_url.clone(),
+        args.beacon_api_url.clone(),
+        args.disperser_rpc.clone(),
+        worker.clone(),
+        args.relays_required_for_readiness,
+    ));
+    let proof_gen_thread: JoinHandle<Result<()>> = tokio::spawn(async move { worker.run().await });
+
+    let freshness_monitor = args.auto_reprove.then(|| {
+        FreshnessMonitor::new(
+            db_pool.clone(),
+            args.rpc_url.clone(),
+            notifier.clone(),
+            args.expiry_margin_blocks,
+        )
+    });
+    let freshness_thread: JoinHandle<Result<()>> = tokio::spawn(async move {
+        let Some(freshness_monitor) = freshness_monitor else {
+            tracing::info!("AUTO_REPROVE not set, expiring proofs are not proven again");
+            return Ok(());
         };
+        tracing::info!("Proving again the unconsumed proofs about to expire");
+        freshness_monitor.run().await
+    });
 
-        let relay_client = RelayClient::new(relay_client_config).await?;
-        let retriever = Arc::new(Mutex::new(RelayPayloadRetriever::new(
-            retriever_config,
-            srs_config,
-            relay_client,
-        )?));
-
-        let rpc_url = args.rpc_url.clone();
-        let cert_verifier_router_addr = args.eigenda_cert_verifier_router_addr;
-
-        let db_pool = db_pool.clone();
-        loop {
-            let blob_id = match retrieve_next_pending_proof(db_pool.clone()).await {
-                Ok(Some(blob_id)) => blob_id,
-                Ok(None) => {
-                    tracing::debug!("No pending proofs found");
-                    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                    continue;
-                }
-                Err(e) => {
-                    tracing::error!("Error retrieving pending proof: {}", e);
-                    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                    continue;
-                }
-            };
-
-            tracing::info!(
-                "Proof generation thread: retrieved request to prove: {}",
-                blob_id
-            );
-
-            let timer = PROOF_GEN_TIME_HISTOGRAM
-                .with_label_values(&[&blob_id])
-                .start_timer();
-
-            match generate_proof(
-                blob_id.clone(),
-                payload_disperser.clone(),
-                retriever.clone(),
-                &srs,
-                rpc_url.clone(),
-                cert_verifier_router_addr,
-                payload_form,
-            )
-            .await
-            {
-                Ok(proof) => {
-                    tracing::info!("Proof gen thread: generated proof for Blob Id {}", blob_id);
-                    // Persist proof in database
-                    store_blob_proof(db_pool.clone(), blob_id, hex::encode(proof)).await?;
-                    PROOF_GEN_SUCCESS_COUNTER.inc();
-                }
-                Err(e) => {
-                    tracing::error!(
-                        "Proof gen thread: error generating proof for Blob Id: {}, error: {}",
-                        blob_id,
-                        e
-                    );
-                    // Mark the proof request as invalid in the database
-                    mark_blob_proof_request_failed(db_pool.clone(), blob_id.clone()).await?;
-                    PROOF_GEN_FAILURE_COUNTER.inc();
-                }
-            };
-
-            timer.observe_duration();
-        }
+    let reorg_monitor = (args.reorg_check_depth > 0).then(|| {
+        ReorgMonitor::new(
+            db_pool.clone(),
+            args.rpc_url.clone(),
+            notifier.clone(),
+            args.reorg_check_depth,
+        )
+    });
+    let reorg_thread: JoinHandle<Result<()>> = tokio::spawn(async move {
+        let Some(reorg_monitor) = reorg_monitor else {
+            tracing::info!("REORG_CHECK_DEPTH set to 0, reorg detection disabled");
+            return Ok(());
+        };
+        tracing::info!("Checking the anchor blocks of the proofs for reorgs");
+        reorg_monitor.run().await
     });
 
+    let rpc_service = service.clone();
     let json_rpc_server_thread: JoinHandle<Result<()>> = tokio::spawn(async move {
-        let mut io = IoHandler::new();
-        let db_pool = db_pool_clone.clone();
-        let payload_disperser = payload_disperser_clone.clone();
-        io.add_method("generate_proof", move |params: Params| {
-            let db_pool = db_pool.clone();
-            let payload_disperser = payload_disperser.clone();
-            async move {
-                PROOF_GEN_REQ_COUNTER.inc();
-
-                let parsed: GenerateProofParams = params.parse().map_err(|_| {
-                    jsonrpc_core::Error::invalid_params(
-                        "Expected a single string parameter 'blob_id'",
-                    )
-                })?;
-                let blob_id = parsed.blob_id;
-                tracing::info!("Received request to generate proof for Blob Id {}", blob_id);
-
-                let blob_key = BlobKey::from_hex(&blob_id)
-                    .map_err(|_| jsonrpc_core::Error::invalid_params("Invalid blob ID"))?;
-                if payload_disperser.get_cert(&blob_key).await.is_err() {
-                    return Err(jsonrpc_core::Error::invalid_params(
-                        "Blob ID not found in EigenDA",
-                    ));
-                }
-
-                if proof_request_exists(db_pool.clone(), blob_id.clone())
-                    .await
-                    .map_err(|_| {
-                        tracing::error!(
-                            "Failed checking if Blob Id {} already has a proof request",
-                            blob_id
-                        );
-                        jsonrpc_core::Error::internal_error()
-                    })?
-                {
-                    return Err(jsonrpc_core::Error::invalid_params(
-                        "Blob ID already submitted",
-                    ));
-                }
-
-                // Persist request in database
-                store_blob_proof_request(db_pool.clone(), blob_id.clone())
-                    .await
-                    .map_err(|_| {
-                        tracing::error!("Failed sending Blob Id {} to prover thread", blob_id);
-                        jsonrpc_core::Error::internal_error()
-                    })?;
-
-                Ok(jsonrpc_core::Value::String(format!(
-                    "Generating Proof for {}",
-                    blob_id
-                )))
-            }
-        });
-
-        let db_pool = db_pool_clone.clone();
-        io.add_method("get_proof", move |params: Params| {
-            PROOF_RET_REQ_COUNTER.inc();
-
-            let db_pool = db_pool.clone();
-            async move {
-                let parsed: GenerateProofParams = params.parse().map_err(|_| {
-                    jsonrpc_core::Error::invalid_params(
-                        "Expected a single string parameter 'blob_id'",
-                    )
-                })?;
-
-                let blob_id = parsed.blob_id;
-                tracing::info!("Received request to get proof for Blob Id {}", blob_id);
-                match retrieve_blob_id_proof(db_pool.clone(), blob_id.clone()).await {
-                    Err(_) | Ok(None) => {
-                        tracing::debug!("Proof for Blob ID {} not found", blob_id);
-                        Err(jsonrpc_core::Error::internal_error())
-                    }
-                    Ok(Some((proof, failed))) => {
-                        if failed {
-                            return Err(jsonrpc_core::Error::invalid_params(
-                                "Proof request for Blob ID was not valid",
-                            ));
-                        }
-
-                        match proof {
-                            None => {
-                                tracing::debug!(
-                                    "Proof for Blob ID {} not found (still queued)",
-                                    blob_id
-                                );
-                                Err(jsonrpc_core::Error {
-                                    code: ErrorCode::ServerError(PROOF_NOT_FOUND_ERROR),
-                                    message: "Proof not found (still queued)".to_string(),
-                                    data: None,
-                                })
-                            }
-                            Some(proof) => Ok(jsonrpc_core::Value::String(proof)),
-                        }
-                    }
-                }
-            }
-        });
-
+        let io = build_rpc_handler(rpc_service);
         let server = ServerBuilder::new(io)
             .start_http(&proving_service_url.clone().parse()?)
             .expect("Unable to start server");
@@ -453,34 +319,54 @@ async fn main() -> Result<()> {
         Ok(())
     });
 
-    let metrics_server_thread = tokio::spawn(async move {
-        tracing::info!("Starting metrics server on port 9100");
-        let server = MetricsServer::http(metrics_url)
-            .map_err(|_| anyhow::anyhow!("Failed to start metrics server"))?;
-        for request in server.incoming_requests() {
-            if request.url() == "/metrics" {
-                let encoder = prometheus::TextEncoder::new();
-                let metric_families = prometheus::gather();
-                let mut buffer = Vec::new();
-                encoder.encode(&metric_families, &mut buffer)?;
+    let admin_server_thread: JoinHandle<Result<()>> = tokio::spawn(async move {
+        let Some(admin_url) = admin_url else {
+            tracing::info!("ADMIN_URL not set, admin JSON RPC methods disabled");
+            return Ok(());
+        };
+        let io = build_admin_rpc_handler(control);
+        let server = ServerBuilder::new(io)
+            .start_http(&admin_url.parse()?)
+            .expect("Unable to start admin server");
+        tracing::info!("Running admin JSON RPC server on {}", admin_url);
+        server.wait();
+        Ok(())
+    });
 
-                let content_type =
-                    Header::from_bytes(&b"Content-Type"[..], &b"text/plain; version=0.0.4"[..])
-                        .map_err(|_| anyhow::anyhow!("Failed parsing header"))?;
+    let grpc_service = service.clone();
+    let grpc_server_thread: JoinHandle<Result<()>> = tokio::spawn(async move {
+        let Some(grpc_url) = grpc_url else {
+            tracing::info!("GRPC_URL not set, gRPC API disabled");
+            return Ok(());
+        };
+        tracing::info!("Running gRPC server on {}", grpc_url);
+        host::grpc::run_grpc_server(grpc_url, grpc_service).await
+    });
 
-                let response = Response::from_data(buffer).with_header(content_type);
-                let _ = request.respond(response);
-            } else {
-                let response = Response::from_string("Not Found").with_status_code(404);
-                let _ = request.respond(response);
-            }
-        }
-        Ok(())
+    let runtime = tokio::runtime::Handle::current();
+    let metrics_runtime = runtime.clone();
+    let rest_server_thread: JoinHandle<Result<()>> = tokio::task::spawn_blocking(move || {
+        let Some(rest_url) = rest_url else {
+            tracing::info!("REST_URL not set, REST API disabled");
+            return Ok(());
+        };
+        tracing::info!("Running REST server on {}", rest_url);
+        host::rest::run_rest_server(rest_url, service, runtime)
+    });
+
+    let metrics_server_thread: JoinHandle<Result<()>> = tokio::task::spawn_blocking(move || {
+        tracing::info!("Starting metrics server on port 9100");
+        run_metrics_server(metrics_url, health, metrics_runtime)
     });
 
     match tokio::try_join!(
         flatten(proof_gen_thread),
+        flatten(freshness_thread),
+        flatten(reorg_thread),
         flatten(json_rpc_server_thread),
+        flatten(admin_server_thread),
+        flatten(rest_server_thread),
+        flatten(grpc_server_thread),
         flatten(metrics_server_thread)
     ) {
         Ok(_) => {
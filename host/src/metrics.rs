@@ -0,0 +1,178 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use client::types::ProofStats;
+use prometheus::{
+    self, exponential_buckets, register_histogram, register_histogram_vec, register_int_counter,
+    register_int_gauge_vec, Encoder, Histogram, HistogramVec, IntCounter, IntGaugeVec,
+};
+use serde::Serialize;
+use tiny_http::{Header, Request, Response, Server};
+use tokio::runtime::Handle;
+
+use crate::health::HealthChecker;
+
+// Prometheus metrics
+lazy_static::lazy_static! {
+    pub static ref PROOF_GEN_REQ_COUNTER: IntCounter =
+    register_int_counter!("proof_requests", "Number of proof generation requests received").unwrap();
+
+    pub static ref PROOF_GEN_SUCCESS_COUNTER: IntCounter =
+    register_int_counter!("proof_generations", "Number of successful proofs generated").unwrap();
+
+    pub static ref PROOF_GEN_FAILURE_COUNTER: IntCounter =
+    register_int_counter!("proof_generation_failures", "Number of failed proof generations").unwrap();
+
+    pub static ref AGGREGATION_SUCCESS_COUNTER: IntCounter =
+    register_int_counter!("proof_aggregations", "Number of successful aggregated proofs generated").unwrap();
+
+    pub static ref AGGREGATION_FAILURE_COUNTER: IntCounter =
+    register_int_counter!("proof_aggregation_failures", "Number of failed proof aggregations").unwrap();
+
+    pub static ref GUEST_INPUT_REUSED_COUNTER: IntCounter =
+    register_int_counter!("guest_inputs_reused", "Number of proof attempts that reused the guest input of a previous attempt").unwrap();
+
+    pub static ref STALE_PROOFS_REPROVED_COUNTER: IntCounter =
+    register_int_counter!("stale_proofs_reproved", "Number of unconsumed proofs about to expire that were queued to be proven again").unwrap();
+
+    pub static ref REORGED_PROOFS_COUNTER: IntCounter =
+    register_int_counter!("reorged_proofs", "Number of proofs or stored guest inputs whose anchor block was reorged out, queued to be proven again").unwrap();
+
+    pub static ref PROOF_RET_REQ_COUNTER: IntCounter =
+    register_int_counter!("proof_retrievals", "Number of proof retrieval requests received").unwrap();
+
+    pub static ref PROOF_GEN_TIME_HISTOGRAM: HistogramVec =
+        register_histogram_vec!(
+            "proof_generation_seconds",
+            "Time taken to generate a proof in seconds",
+            &["blob_id"]
+        ).unwrap();
+
+    pub static ref PROOF_PAYLOAD_SIZE_HISTOGRAM: Histogram =
+        register_histogram!(
+            "proof_payload_bytes",
+            "Size of the proven blob payloads in bytes",
+            exponential_buckets(1024.0, 2.0, 13).unwrap()
+        ).unwrap();
+
+    pub static ref GUEST_PROVING_TIME_HISTOGRAM: Histogram =
+        register_histogram!(
+            "guest_proving_seconds",
+            "Time taken by the prover backend to prove the guest in seconds",
+            exponential_buckets(1.0, 2.0, 14).unwrap()
+        ).unwrap();
+
+    pub static ref PROOF_COMPRESSION_TIME_HISTOGRAM: Histogram =
+        register_histogram!(
+            "proof_compression_seconds",
+            "Time taken to compress the succinct receipts into Groth16 receipts in seconds",
+            exponential_buckets(1.0, 2.0, 10).unwrap()
+        ).unwrap();
+
+    pub static ref GUEST_TOTAL_CYCLES_HISTOGRAM: Histogram =
+        register_histogram!(
+            "guest_total_cycles",
+            "Total cycles of the proven guest executions",
+            exponential_buckets(1048576.0, 2.0, 12).unwrap()
+        ).unwrap();
+
+    pub static ref GUEST_USER_CYCLES_HISTOGRAM: Histogram =
+        register_histogram!(
+            "guest_user_cycles",
+            "User cycles of the proven guest executions",
+            exponential_buckets(1048576.0, 2.0, 12).unwrap()
+        ).unwrap();
+
+    pub static ref GUEST_PAGING_CYCLES_HISTOGRAM: Histogram =
+        register_histogram!(
+            "guest_paging_cycles",
+            "Paging cycles of the proven guest executions",
+            exponential_buckets(65536.0, 2.0, 12).unwrap()
+        ).unwrap();
+
+    pub static ref GUEST_SEGMENTS_HISTOGRAM: Histogram =
+        register_histogram!(
+            "guest_segments",
+            "Number of segments of the proven guest executions",
+            exponential_buckets(1.0, 2.0, 12).unwrap()
+        ).unwrap();
+
+    pub static ref PROVER_BACKEND_INFO: IntGaugeVec =
+        register_int_gauge_vec!(
+            "prover_backend_info",
+            "Prover backend the proofs are generated with, set to 1 for the selected backend",
+            &["backend"]
+        ).unwrap();
+}
+
+/// Records the statistics of a generated proof.
+pub fn observe_proof_stats(stats: &ProofStats) {
+    PROOF_PAYLOAD_SIZE_HISTOGRAM.observe(stats.payload_size as f64);
+    GUEST_PROVING_TIME_HISTOGRAM.observe(stats.proving_secs);
+    if let Some(execution) = &stats.execution {
+        GUEST_TOTAL_CYCLES_HISTOGRAM.observe(execution.total_cycles as f64);
+        GUEST_USER_CYCLES_HISTOGRAM.observe(execution.user_cycles as f64);
+        GUEST_PAGING_CYCLES_HISTOGRAM.observe(execution.paging_cycles as f64);
+        GUEST_SEGMENTS_HISTOGRAM.observe(execution.segments as f64);
+    }
+}
+
+/// Runs the metrics server, blocking the current thread. Besides the prometheus
+/// metrics on `/metrics`, it serves the `/healthz` and `/readyz` probes.
+pub fn run_metrics_server(
+    metrics_url: String,
+    health: Arc<HealthChecker>,
+    handle: Handle,
+) -> Result<()> {
+    let server =
+        Server::http(metrics_url).map_err(|_| anyhow::anyhow!("Failed to start metrics server"))?;
+    for request in server.incoming_requests() {
+        let health = health.clone();
+        let runtime = handle.clone();
+        handle.spawn_blocking(move || handle_request(request, health, runtime));
+    }
+    Ok(())
+}
+
+fn handle_request(request: Request, health: Arc<HealthChecker>, handle: Handle) {
+    let response = match request.url() {
+        "/metrics" => metrics_response(),
+        "/healthz" => {
+            let report = health.liveness();
+            json_response(if report.ok { 200 } else { 503 }, &report)
+        }
+        "/readyz" => {
+            let report = handle.block_on(health.readiness());
+            json_response(if report.ok { 200 } else { 503 }, &report)
+        }
+        _ => Response::from_string("Not Found")
+            .with_status_code(404)
+            .boxed(),
+    };
+    let _ = request.respond(response);
+}
+
+fn metrics_response() -> tiny_http::ResponseBox {
+    let encoder = prometheus::TextEncoder::new();
+    let metric_families = prometheus::gather();
+    let mut buffer = Vec::new();
+    if let Err(e) = encoder.encode(&metric_families, &mut buffer) {
+        tracing::error!("Failed encoding metrics: {}", e);
+        return Response::empty(500).boxed();
+    }
+
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"text/plain; version=0.0.4"[..])
+        .expect("static header is valid");
+    Response::from_data(buffer)
+        .with_header(content_type)
+        .boxed()
+}
+
+fn json_response<T: Serialize>(status_code: u16, body: &T) -> tiny_http::ResponseBox {
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("static header is valid");
+    Response::from_data(serde_json::to_vec(body).unwrap_or_default())
+        .with_status_code(status_code)
+        .with_header(content_type)
+        .boxed()
+}
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use sqlx::{Pool, Postgres};
+use tokio::sync::{broadcast, Mutex};
+
+use crate::db::retrieve_blob_id_proof;
+use crate::service::ProofStatus;
+
+/// Number of notifications kept for subscribers that are behind.
+/// Lagging subscribers re-check the database, so no notification is lost for them.
+const NOTIFICATION_CAPACITY: usize = 256;
+
+/// In-process notifications of proof requests leaving the queue (proven, failed or cancelled),
+/// or put back in it (retries, recompressions and the monitors), published so waiters don't need to poll the database.
+#[derive(Clone)]
+pub struct ProofNotifier {
+    sender: broadcast::Sender<ProofStatus>,
+}
+
+impl Default for ProofNotifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProofNotifier {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(NOTIFICATION_CAPACITY);
+        Self { sender }
+    }
+
+    /// Notifies the new status of a proof request to the current subscribers.
+    pub fn notify(&self, status: ProofStatus) {
+        // Sending only fails if nobody is waiting
+        let _ = self.sender.send(status);
+    }
+
+    /// Notifies the status of the proof request as stored in the database.
+    pub async fn notify_stored(&self, db_pool: Arc<Mutex<Pool<Postgres>>>, blob_id: String) {
+        match retrieve_blob_id_proof(db_pool, blob_id.clone()).await {
+            Ok(Some(record)) => self.notify(record.into_status()),
+            Ok(None) => {}
+            // The waiters re-check the database when they time out
+            Err(e) => tracing::error!("Failed retrieving status of Blob Id {}: {}", blob_id, e),
+        }
+    }
+
+    /// Subscribes to the notifications published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<ProofStatus> {
+        self.sender.subscribe()
+    }
+}
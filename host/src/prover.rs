@@ -0,0 +1,381 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use clap::ValueEnum;
+use client::types::{ExecutionStats, ReceiptKind};
+use methods::{AGGREGATOR_ELF, GUEST_ELF};
+use risc0_zkvm::{
+    compute_image_id, ExecutorEnv, FakeReceipt, InnerReceipt, LocalProver, Prover, ProverOpts,
+    Receipt, ReceiptClaim, Session, SessionStats, VerifierContext,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::guest_caller::{execute_aggregator, execute_guest, guest_output, AggregationInput};
+
+/// Prover backends the proofs can be generated with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProverBackendKind {
+    /// Local prover running on the CPU.
+    Cpu,
+    /// Local prover running on an NVIDIA GPU, needs the host built with the `cuda` feature.
+    Cuda,
+    /// Remote prover, reached on `REMOTE_PROVER_URL`.
+    Remote,
+    /// Executes the guest and returns fake receipts, like risc0 dev mode. Needs dev mode enabled.
+    Dev,
+    /// Deterministic mock returning fake receipts, without running the guest. Needs dev mode enabled.
+    Mock,
+}
+
+impl ProverBackendKind {
+    /// The local prover the host was built for.
+    pub fn local() -> Self {
+        if cfg!(feature = "cuda") {
+            ProverBackendKind::Cuda
+        } else {
+            ProverBackendKind::Cpu
+        }
+    }
+}
+
+impl std::fmt::Display for ProverBackendKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ProverBackendKind::Cpu => "cpu",
+            ProverBackendKind::Cuda => "cuda",
+            ProverBackendKind::Remote => "remote",
+            ProverBackendKind::Dev => "dev",
+            ProverBackendKind::Mock => "mock",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Receipt generated by a prover backend.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProveOutput {
+    pub receipt: Receipt,
+    /// Statistics of the proven execution, if the backend reports them.
+    pub stats: Option<ExecutionStats>,
+}
+
+/// Options the guest is proven with to get the given kind of receipt. Groth16 receipts are
+/// proven succinct, and then compressed with [`ProverBackend::compress`].
+pub fn prover_opts(receipt_kind: ReceiptKind) -> ProverOpts {
+    match receipt_kind {
+        ReceiptKind::Composite => ProverOpts::composite(),
+        ReceiptKind::Succinct | ReceiptKind::Groth16 => ProverOpts::succinct(),
+    }
+}
+
+/// Body of the remote prover `POST {url}/aggregate` requests.
+#[derive(Serialize, Deserialize)]
+pub struct AggregateRequest {
+    /// Stdin of the aggregation guest, see `AggregationInput::to_stdin`.
+    pub stdin: Vec<u8>,
+    /// Succinct receipts of the aggregated blobs.
+    pub receipts: Vec<Receipt>,
+}
+
+fn execution_stats(stats: &SessionStats) -> ExecutionStats {
+    ExecutionStats {
+        total_cycles: stats.total_cycles,
+        user_cycles: stats.user_cycles,
+        paging_cycles: stats.paging_cycles,
+        segments: stats.segments as u64,
+    }
+}
+
+/// Generates the receipts of the guest.
+#[async_trait]
+pub trait ProverBackend: Send + Sync {
+    /// Backend kind, reported in logs.
+    fn kind(&self) -> ProverBackendKind;
+
+    /// Proves the guest with the given stdin (see `GuestInput::to_stdin`), returning a
+    /// composite receipt if requested, or a succinct one otherwise (see [`prover_opts`]).
+    async fn prove(&self, stdin: Vec<u8>, receipt_kind: ReceiptKind) -> Result<ProveOutput>;
+
+    /// Proves the aggregation guest with the given stdin (see `AggregationInput::to_stdin`),
+    /// with the succinct receipts of the aggregated blobs as the assumptions its
+    /// `env::verify` calls are resolved with. Returns a succinct receipt.
+    async fn aggregate(&self, stdin: Vec<u8>, receipts: Vec<Receipt>) -> Result<ProveOutput>;
+
+    /// Compresses a succinct receipt into a Groth16 receipt, verifiable onchain.
+    async fn compress(&self, receipt: Receipt) -> Result<Receipt>;
+}
+
+/// Builds the prover backend of the given kind.
+/// The backends generating fake receipts are refused unless dev mode is enabled, and the local
+/// provers are refused if risc0's own dev mode would make them generate fake receipts.
+pub fn build_prover_backend(
+    kind: ProverBackendKind,
+    remote_prover_url: Option<Url>,
+    dev_mode: bool,
+) -> Result<Arc<dyn ProverBackend>> {
+    if matches!(kind, ProverBackendKind::Dev | ProverBackendKind::Mock) && !dev_mode {
+        anyhow::bail!(
+            "The {} prover backend generates fake proofs, it needs dev mode enabled (PROVING_SERVICE_DEV_MODE=1)",
+            kind
+        );
+    }
+    let backend: Arc<dyn ProverBackend> = match kind {
+        ProverBackendKind::Cpu | ProverBackendKind::Cuda => {
+            if kind != ProverBackendKind::local() {
+                anyhow::bail!(
+                    "The {} prover backend is not available, the host was built for the {} prover",
+                    kind,
+                    ProverBackendKind::local()
+                );
+            }
+            if ProverOpts::default().dev_mode() {
+                anyhow::bail!(
+                    "RISC0_DEV_MODE is set, the {} prover backend would generate fake proofs. Unset it, or use the dev prover backend with PROVING_SERVICE_DEV_MODE=1",
+                    kind
+                );
+            }
+            Arc::new(LocalProverBackend)
+        }
+        ProverBackendKind::Remote => {
+            let url = remote_prover_url.ok_or_else(|| {
+                anyhow::anyhow!("REMOTE_PROVER_URL is required by the remote prover backend")
+            })?;
+            Arc::new(RemoteProverBackend::new(url, dev_mode))
+        }
+        ProverBackendKind::Dev => {
+            tracing::warn!("Running in dev mode, the generated proofs are fake and NOT valid");
+            Arc::new(DevProverBackend)
+        }
+        ProverBackendKind::Mock => {
+            tracing::warn!("Using the mock prover backend, the generated proofs are NOT valid");
+            Arc::new(MockProverBackend::new()?)
+        }
+    };
+    Ok(backend)
+}
+
+/// Proves the guest on this machine, on the CPU or on the GPU if built with the `cuda` feature.
+pub struct LocalProverBackend;
+
+#[async_trait]
+impl ProverBackend for LocalProverBackend {
+    fn kind(&self) -> ProverBackendKind {
+        ProverBackendKind::local()
+    }
+
+    async fn prove(&self, stdin: Vec<u8>, receipt_kind: ReceiptKind) -> Result<ProveOutput> {
+        prove_locally(GUEST_ELF, stdin, vec![], prover_opts(receipt_kind)).await
+    }
+
+    async fn aggregate(&self, stdin: Vec<u8>, receipts: Vec<Receipt>) -> Result<ProveOutput> {
+        prove_locally(AGGREGATOR_ELF, stdin, receipts, ProverOpts::succinct()).await
+    }
+
+    async fn compress(&self, receipt: Receipt) -> Result<Receipt> {
+        tokio::task::spawn_blocking(move || {
+            LocalProver::new("local")
+                .compress(&ProverOpts::groth16(), &receipt)
+                .context("failed to compress receipt")
+        })
+        .await?
+    }
+}
+
+/// Proves the given ELF with the local prover and the given options,
+/// resolving its assumptions with the given receipts.
+async fn prove_locally(
+    elf: &'static [u8],
+    stdin: Vec<u8>,
+    assumptions: Vec<Receipt>,
+    opts: ProverOpts,
+) -> Result<ProveOutput> {
+    let prove_info = tokio::task::spawn_blocking(move || -> Result<_> {
+        let mut env = ExecutorEnv::builder();
+        for assumption in assumptions {
+            env.add_assumption(assumption);
+        }
+        let env = env.write_slice(&stdin).build()?;
+        LocalProver::new("local")
+            .prove_with_ctx(env, &VerifierContext::default(), elf, &opts)
+            .context("failed to run executor")
+    })
+    .await??;
+    Ok(ProveOutput {
+        stats: Some(execution_stats(&prove_info.stats)),
+        receipt: prove_info.receipt,
+    })
+}
+
+/// Proves the guest on a remote prover: the guest stdin is sent in the body of a
+/// `POST {url}/prove?receipt_kind={kind}` request, which answers with the JSON encoded [`ProveOutput`].
+/// Aggregations are proven with a `POST {url}/aggregate` request, which receives a JSON encoded
+/// [`AggregateRequest`] and answers with the JSON encoded [`ProveOutput`].
+/// Receipts are compressed with a `POST {url}/compress` request, which receives and
+/// answers with a JSON encoded receipt.
+/// See the `remote-prover` binary, which serves this API with a local backend.
+/// Fake receipts are refused unless dev mode is enabled.
+pub struct RemoteProverBackend {
+    url: Url,
+    http: reqwest::Client,
+    dev_mode: bool,
+}
+
+impl RemoteProverBackend {
+    pub fn new(url: Url, dev_mode: bool) -> Self {
+        Self {
+            url,
+            http: reqwest::Client::new(),
+            dev_mode,
+        }
+    }
+}
+
+#[async_trait]
+impl ProverBackend for RemoteProverBackend {
+    fn kind(&self) -> ProverBackendKind {
+        ProverBackendKind::Remote
+    }
+
+    async fn prove(&self, stdin: Vec<u8>, receipt_kind: ReceiptKind) -> Result<ProveOutput> {
+        let request = self
+            .http
+            .post(self.url.join("prove")?)
+            .query(&[("receipt_kind", receipt_kind.as_str())])
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .body(stdin);
+        let output: ProveOutput = self.send(request).await?;
+        self.check_receipt(&output.receipt)?;
+        Ok(output)
+    }
+
+    async fn aggregate(&self, stdin: Vec<u8>, receipts: Vec<Receipt>) -> Result<ProveOutput> {
+        let request = self
+            .http
+            .post(self.url.join("aggregate")?)
+            .json(&AggregateRequest { stdin, receipts });
+        let output: ProveOutput = self.send(request).await?;
+        self.check_receipt(&output.receipt)?;
+        Ok(output)
+    }
+
+    async fn compress(&self, receipt: Receipt) -> Result<Receipt> {
+        let request = self.http.post(self.url.join("compress")?).json(&receipt);
+        let receipt: Receipt = self.send(request).await?;
+        self.check_receipt(&receipt)?;
+        Ok(receipt)
+    }
+}
+
+impl RemoteProverBackend {
+    async fn send<T: serde::de::DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T> {
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Remote prover failed with status {}: {}", status, body);
+        }
+        Ok(response.json().await?)
+    }
+
+    fn check_receipt(&self, receipt: &Receipt) -> Result<()> {
+        if matches!(receipt.inner, InnerReceipt::Fake(_)) && !self.dev_mode {
+            anyhow::bail!("Remote prover returned a fake receipt, and dev mode is disabled");
+        }
+        Ok(())
+    }
+}
+
+/// Executes the guest and returns a fake receipt of the execution, like risc0 dev mode.
+/// The guest assertions are checked and the journal is the one it commits, but nothing is proven.
+pub struct DevProverBackend;
+
+#[async_trait]
+impl ProverBackend for DevProverBackend {
+    fn kind(&self) -> ProverBackendKind {
+        ProverBackendKind::Dev
+    }
+
+    async fn prove(&self, stdin: Vec<u8>, _receipt_kind: ReceiptKind) -> Result<ProveOutput> {
+        fake_execution(move || execute_guest(&stdin)).await
+    }
+
+    async fn aggregate(&self, stdin: Vec<u8>, receipts: Vec<Receipt>) -> Result<ProveOutput> {
+        fake_execution(move || execute_aggregator(&stdin, receipts)).await
+    }
+
+    async fn compress(&self, receipt: Receipt) -> Result<Receipt> {
+        // Fake receipts can't be compressed
+        Ok(receipt)
+    }
+}
+
+/// Runs the execution and returns a fake receipt of its claim and journal.
+async fn fake_execution(
+    execute: impl FnOnce() -> Result<Session> + Send + 'static,
+) -> Result<ProveOutput> {
+    let (claim, journal, stats) = tokio::task::spawn_blocking(move || -> Result<_> {
+        let session = execute()?;
+        let journal = session.journal.clone().map(|journal| journal.bytes);
+        let stats = execution_stats(&session.stats());
+        Ok((session.claim()?, journal.unwrap_or_default(), stats))
+    })
+    .await??;
+    Ok(ProveOutput {
+        receipt: Receipt::new(InnerReceipt::Fake(FakeReceipt::new(claim)), journal),
+        stats: Some(stats),
+    })
+}
+
+/// Returns fake receipts without running the guest. The journal is the ABI encoded output the
+/// guest would commit for the input (see [`guest_output`]), so the same input always gets the
+/// same receipt, and its proof is decoded and aggregated like a real one.
+/// Aggregations get a fake receipt in the same way, with the output of the aggregation guest.
+pub struct MockProverBackend {
+    image_id: risc0_zkvm::sha::Digest,
+    aggregator_image_id: risc0_zkvm::sha::Digest,
+}
+
+impl MockProverBackend {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            image_id: compute_image_id(GUEST_ELF)?,
+            aggregator_image_id: compute_image_id(AGGREGATOR_ELF)?,
+        })
+    }
+}
+
+fn mock_receipt(image_id: risc0_zkvm::sha::Digest, journal: Vec<u8>) -> ProveOutput {
+    let claim = ReceiptClaim::ok(image_id, journal.clone());
+    ProveOutput {
+        receipt: Receipt::new(InnerReceipt::Fake(FakeReceipt::new(claim)), journal),
+        // The guest is not run
+        stats: None,
+    }
+}
+
+#[async_trait]
+impl ProverBackend for MockProverBackend {
+    fn kind(&self) -> ProverBackendKind {
+        ProverBackendKind::Mock
+    }
+
+    async fn prove(&self, stdin: Vec<u8>, _receipt_kind: ReceiptKind) -> Result<ProveOutput> {
+        let journal = guest_output(&stdin)?.abi_encode();
+        Ok(mock_receipt(self.image_id, journal))
+    }
+
+    async fn aggregate(&self, stdin: Vec<u8>, _receipts: Vec<Receipt>) -> Result<ProveOutput> {
+        let journal = AggregationInput::from_stdin(&stdin)?.output()?.abi_encode();
+        Ok(mock_receipt(self.aggregator_image_id, journal))
+    }
+
+    async fn compress(&self, receipt: Receipt) -> Result<Receipt> {
+        // Fake receipts can't be compressed
+        Ok(receipt)
+    }
+}
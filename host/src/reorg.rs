@@ -0,0 +1,140 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use alloy_primitives::B256;
+use anyhow::Result;
+use sqlx::{Pool, Postgres};
+use tokio::sync::Mutex;
+use url::Url;
+
+use crate::chain::EthRpcClient;
+use crate::db::{
+    requeue_reorged_blob_proof, retrieve_recently_anchored_blob_proofs, BlobProofRecord,
+};
+use crate::metrics::REORGED_PROOFS_COUNTER;
+use crate::notifier::ProofNotifier;
+
+/// Time between two checks of the anchor blocks of the recent proofs.
+pub const REORG_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Maximum number of proofs checked on each iteration.
+const MAX_CHECKED_PROOFS: i64 = 1000;
+
+/// Re-checks the anchor block hash of the proofs, and of the stored guest inputs of the queued
+/// requests, anchored in the last `depth` blocks against the canonical chain. Those whose anchor
+/// block was reorged out are invalidated and queued to be proven again, as their steel
+/// environment commitment can't be verified onchain.
+pub struct ReorgMonitor {
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    eth_rpc: EthRpcClient,
+    notifier: ProofNotifier,
+    depth: u64,
+}
+
+impl ReorgMonitor {
+    pub fn new(
+        db_pool: Arc<Mutex<Pool<Postgres>>>,
+        rpc_url: Url,
+        notifier: ProofNotifier,
+        depth: u64,
+    ) -> Self {
+        Self {
+            db_pool,
+            eth_rpc: EthRpcClient::new(rpc_url),
+            notifier,
+            depth,
+        }
+    }
+
+    /// Runs the check loop. Failed checks are logged and retried on the next iteration.
+    pub async fn run(&self) -> Result<()> {
+        loop {
+            if let Err(e) = self.requeue_reorged_proofs().await {
+                tracing::warn!("Failed checking the anchor blocks of the proofs: {}", e);
+            }
+            tokio::time::sleep(REORG_CHECK_INTERVAL).await;
+        }
+    }
+
+    async fn requeue_reorged_proofs(&self) -> Result<()> {
+        let latest_block = self.eth_rpc.block_number().await?;
+        let records = retrieve_recently_anchored_blob_proofs(
+            self.db_pool.clone(),
+            latest_block.saturating_sub(self.depth),
+            MAX_CHECKED_PROOFS,
+        )
+        .await?;
+
+        for reorged in find_reorged_anchors(&self.eth_rpc, records).await? {
+            if !requeue_reorged_blob_proof(
+                self.db_pool.clone(),
+                reorged.blob_id.clone(),
+                reorged.anchor_hash.clone(),
+            )
+            .await?
+            {
+                continue;
+            }
+            tracing::warn!(
+                "Anchor block {} ({}) of Blob Id {} was reorged out, canonical hash is {:#x}, queued to prove it again",
+                reorged.anchor_block,
+                reorged.anchor_hash,
+                reorged.blob_id,
+                reorged.canonical_hash
+            );
+            REORGED_PROOFS_COUNTER.inc();
+            self.notifier
+                .notify_stored(self.db_pool.clone(), reorged.blob_id)
+                .await;
+        }
+        Ok(())
+    }
+}
+
+/// Proof request whose proof or stored guest input is anchored to a block reorged out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReorgedAnchor {
+    pub blob_id: String,
+    pub anchor_block: u64,
+    /// Hex encoded hash of the reorged anchor block, as stored.
+    pub anchor_hash: String,
+    /// Hash of the canonical block at the same height.
+    pub canonical_hash: B256,
+}
+
+/// Checks the anchor block hash of the records against the canonical chain, and returns those
+/// that were reorged out. Records anchored to a block the RPC node has not caught up with yet
+/// are left for a later check.
+pub async fn find_reorged_anchors(
+    eth_rpc: &EthRpcClient,
+    records: Vec<BlobProofRecord>,
+) -> Result<Vec<ReorgedAnchor>> {
+    // Proofs are often anchored to the same block, each one is only requested once
+    let mut canonical_hashes: BTreeMap<u64, Option<B256>> = BTreeMap::new();
+    let mut reorged = Vec::new();
+    for record in records {
+        let (Some(anchor_block), Some(anchor_hash)) = (record.anchor_block, record.anchor_hash)
+        else {
+            continue;
+        };
+        let canonical_hash = match canonical_hashes.get(&anchor_block) {
+            Some(canonical_hash) => *canonical_hash,
+            None => {
+                let canonical_hash = eth_rpc.block_hash(anchor_block).await?;
+                canonical_hashes.insert(anchor_block, canonical_hash);
+                canonical_hash
+            }
+        };
+        let Some(canonical_hash) = canonical_hash else {
+            continue;
+        };
+        if anchor_hash.parse::<B256>()? != canonical_hash {
+            reorged.push(ReorgedAnchor {
+                blob_id: record.blob_id,
+                anchor_block,
+                anchor_hash,
+                canonical_hash,
+            });
+        }
+    }
+    Ok(reorged)
+}
@@ -0,0 +1,357 @@
+use std::{io::Read, sync::Arc};
+
+use anyhow::Result;
+use client::types::GenerateProofParams;
+use percent_encoding::percent_decode_str;
+use serde::Serialize;
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server};
+use tokio::runtime::Handle;
+
+use crate::service::{ProofState, ProvingService, ServiceError};
+
+type JsonResponse = Response<std::io::Cursor<Vec<u8>>>;
+
+/// Maximum size of a request body, enough for a hex encoded 16 MiB payload and its cert.
+pub const MAX_BODY_BYTES: u64 = 40 * 1024 * 1024;
+
+/// Runs the REST API server, blocking the current thread.
+/// Each request is handled on its own blocking task of the given runtime,
+/// using the same handlers as the JSON RPC server.
+pub fn run_rest_server(
+    rest_url: String,
+    service: Arc<ProvingService>,
+    handle: Handle,
+) -> Result<()> {
+    let server =
+        Server::http(rest_url).map_err(|_| anyhow::anyhow!("Failed to start REST server"))?;
+    for request in server.incoming_requests() {
+        let service = service.clone();
+        let runtime = handle.clone();
+        handle.spawn_blocking(move || handle_request(request, service, runtime));
+    }
+    Ok(())
+}
+
+/// Request of the REST API, routed from its method and URL, with its path and query
+/// parameters decoded.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Route {
+    /// `POST /proofs`
+    GenerateProof,
+    /// `GET /proofs`, with the `state` filter if given.
+    ListProofs(Option<ProofState>),
+    /// `GET /proofs/{blob_id}`
+    GetProof(String),
+    /// `GET /info`
+    ServiceInfo,
+    /// `GET /openapi.json`
+    OpenApi,
+}
+
+/// Error answered to a REST request.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RestError {
+    pub status_code: u16,
+    pub message: String,
+}
+
+impl RestError {
+    fn new(status_code: u16, message: impl Into<String>) -> Self {
+        Self {
+            status_code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<ServiceError> for RestError {
+    fn from(err: ServiceError) -> Self {
+        let status_code = match err {
+            ServiceError::InvalidParams(_) => 400,
+            ServiceError::NotFound | ServiceError::AggregationNotFound => 404,
+            ServiceError::AlreadySubmitted | ServiceError::InvalidState(_) => 409,
+            ServiceError::Queued | ServiceError::Failed => 422,
+            ServiceError::Internal => 500,
+        };
+        Self::new(status_code, err.to_string())
+    }
+}
+
+fn handle_request(mut request: Request, service: Arc<ProvingService>, handle: Handle) {
+    let response = match read_request(&mut request) {
+        Ok((Route::GenerateProof, body)) => handle.block_on(post_proof(&service, &body)),
+        Ok((Route::ListProofs(state), _)) => handle.block_on(list_proofs(&service, state)),
+        Ok((Route::GetProof(blob_id), _)) => handle.block_on(get_proof(&service, blob_id)),
+        Ok((Route::ServiceInfo, _)) => json_response(200, &service.get_service_info()),
+        Ok((Route::OpenApi, _)) => json_response(200, &openapi_document()),
+        Err(err) => error_response(err.status_code, &err.message),
+    };
+    let _ = request.respond(response);
+}
+
+/// Reads the body of the POST requests, and routes the request.
+fn read_request(request: &mut Request) -> Result<(Route, String), RestError> {
+    let body = if *request.method() == Method::Post {
+        read_body(request.as_reader())?
+    } else {
+        String::new()
+    };
+    Ok((route(request.method(), request.url())?, body))
+}
+
+/// Routes a request from its method and URL.
+pub fn route(method: &Method, url: &str) -> Result<Route, RestError> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    match (method, path.trim_end_matches('/')) {
+        (Method::Post, "/proofs") => Ok(Route::GenerateProof),
+        (Method::Get, "/proofs") => Ok(Route::ListProofs(requested_state(query)?)),
+        (Method::Get, "/info") => Ok(Route::ServiceInfo),
+        (Method::Get, "/openapi.json") => Ok(Route::OpenApi),
+        (Method::Get, route) => match route.strip_prefix("/proofs/") {
+            Some(blob_id) if !blob_id.contains('/') => percent_decode_str(blob_id)
+                .decode_utf8()
+                .map(|blob_id| Route::GetProof(blob_id.into_owned()))
+                .map_err(|_| RestError::new(400, "Blob ID is not valid UTF-8")),
+            _ => Err(RestError::new(404, "Not Found")),
+        },
+        _ => Err(RestError::new(404, "Not Found")),
+    }
+}
+
+/// State filter of the `GET /proofs` query, if given.
+fn requested_state(query: &str) -> Result<Option<ProofState>, RestError> {
+    let mut state = None;
+    for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+        if key == "state" {
+            let parsed = value
+                .parse::<ProofState>()
+                .map_err(|e| ServiceError::InvalidParams(e.to_string()))?;
+            state = Some(parsed);
+        }
+    }
+    Ok(state)
+}
+
+/// Reads a request body, refusing bodies larger than [`MAX_BODY_BYTES`].
+pub fn read_body(reader: impl Read) -> Result<String, RestError> {
+    let mut body = Vec::new();
+    reader
+        .take(MAX_BODY_BYTES + 1)
+        .read_to_end(&mut body)
+        .map_err(|_| RestError::new(400, "Failed reading request body"))?;
+    if body.len() as u64 > MAX_BODY_BYTES {
+        return Err(RestError::new(
+            413,
+            format!("Request body exceeds {} bytes", MAX_BODY_BYTES),
+        ));
+    }
+    String::from_utf8(body).map_err(|_| RestError::new(400, "Request body is not valid UTF-8"))
+}
+
+async fn post_proof(service: &ProvingService, body: &str) -> JsonResponse {
+    let params: GenerateProofParams = match serde_json::from_str(body) {
+        Ok(params) => params,
+        Err(_) => {
+            return error_response(
+                400,
+                "Expected a JSON body with a string field 'blob_id' and optional string fields 'eigenda_cert', 'payload', 'receipt_kind' (composite, succinct or groth16) and 'anchor_policy' (latest, latest-<confirmations>, <block number> or reference)",
+            )
+        }
+    };
+    match service.generate_proof(params).await {
+        Ok(status) => json_response(202, &status),
+        Err(e) => service_error_response(e),
+    }
+}
+
+async fn get_proof(service: &ProvingService, blob_id: String) -> JsonResponse {
+    match service.get_proof_status(blob_id).await {
+        Ok(status) => json_response(200, &status),
+        Err(e) => service_error_response(e),
+    }
+}
+
+async fn list_proofs(service: &ProvingService, state: Option<ProofState>) -> JsonResponse {
+    match service.list_proofs(state).await {
+        Ok(proofs) => json_response(200, &proofs),
+        Err(e) => service_error_response(e),
+    }
+}
+
+fn service_error_response(err: ServiceError) -> JsonResponse {
+    let err = RestError::from(err);
+    error_response(err.status_code, &err.message)
+}
+
+fn error_response(status_code: u16, message: &str) -> JsonResponse {
+    json_response(status_code, &json!({ "error": message }))
+}
+
+fn json_response<T: Serialize>(status_code: u16, body: &T) -> JsonResponse {
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("static header is valid");
+    let body = serde_json::to_vec(body).unwrap_or_default();
+    Response::from_data(body)
+        .with_status_code(status_code)
+        .with_header(content_type)
+}
+
+/// OpenAPI description of the REST API, served on `GET /openapi.json`.
+pub fn openapi_document() -> serde_json::Value {
+    let error = json!({ "$ref": "#/components/schemas/Error" });
+    let blob_id_param = json!({
+        "name": "blob_id",
+        "in": "path",
+        "required": true,
+        "description": "Hex encoded EigenDA blob key",
+        "schema": { "type": "string" }
+    });
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "EigenDA Proving Service",
+            "description": "Generates risc0 proofs of EigenDA certificate validity and blob commitment equivalence",
+            "version": env!("CARGO_PKG_VERSION")
+        },
+        "paths": {
+            "/proofs": {
+                "post": {
+                    "summary": "Request the generation of a proof for a blob",
+                    "operationId": "generate_proof",
+                    "requestBody": {
+                        "required": true,
+                        "content": { "application/json": { "schema": { "$ref": "#/components/schemas/GenerateProofRequest" } } }
+                    },
+                    "responses": {
+                        "202": { "description": "Proof request queued", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/ProofStatus" } } } },
+                        "400": { "description": "Invalid blob ID, EigenDA cert or payload, or blob not found in EigenDA", "content": { "application/json": { "schema": error } } },
+                        "409": { "description": "Blob ID already submitted", "content": { "application/json": { "schema": error } } },
+                        "413": { "description": "Request body larger than 40 MiB", "content": { "application/json": { "schema": error } } },
+                        "500": { "description": "Internal error", "content": { "application/json": { "schema": error } } }
+                    }
+                },
+                "get": {
+                    "summary": "List proof requests, optionally filtered by state",
+                    "operationId": "list_proofs",
+                    "parameters": [{
+                        "name": "state",
+                        "in": "query",
+                        "required": false,
+                        "schema": { "$ref": "#/components/schemas/ProofState" }
+                    }],
+                    "responses": {
+                        "200": { "description": "Proof requests, oldest first", "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/ProofStatus" } } } } },
+                        "400": { "description": "Invalid state", "content": { "application/json": { "schema": error } } },
+                        "500": { "description": "Internal error", "content": { "application/json": { "schema": error } } }
+                    }
+                }
+            },
+            "/info": {
+                "get": {
+                    "summary": "Get the configuration the proofs are generated with (guest image ID, verifier parameters, chain, ...)",
+                    "operationId": "get_service_info",
+                    "responses": {
+                        "200": { "description": "Service configuration", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/ServiceInfo" } } } }
+                    }
+                }
+            },
+            "/proofs/{blob_id}": {
+                "get": {
+                    "summary": "Get the status of a proof request, including the proof once generated",
+                    "operationId": "get_proof",
+                    "parameters": [blob_id_param],
+                    "responses": {
+                        "200": { "description": "Proof request status", "content": { "application/json": { "schema": { "$ref": "#/components/schemas/ProofStatus" } } } },
+                        "404": { "description": "Blob ID was never submitted", "content": { "application/json": { "schema": error } } },
+                        "500": { "description": "Internal error", "content": { "application/json": { "schema": error } } }
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "ProofState": { "type": "string", "enum": ["queued", "proven", "failed", "cancelled"] },
+                "ReceiptKind": { "type": "string", "enum": ["composite", "succinct", "groth16"], "default": "groth16", "description": "Kind of receipt the proof is generated from, only groth16 proofs verify onchain" },
+                "AnchorPolicy": { "type": "string", "pattern": "^(latest|latest-[0-9]+|[0-9]+|reference)$", "description": "Block the cert validity is proven at: the latest block, a number of confirmations behind it, a block number, or the cert reference block" },
+                "GenerateProofRequest": {
+                    "type": "object",
+                    "required": ["blob_id"],
+                    "properties": {
+                        "blob_id": { "type": "string", "description": "Hex encoded EigenDA blob key" },
+                        "eigenda_cert": { "type": "string", "description": "Hex encoded ABI EigenDACert of the blob, used instead of requesting it from the disperser" },
+                        "payload": { "type": "string", "description": "Hex encoded payload of the blob, requires eigenda_cert. Checked against the cert blob commitment and used instead of retrieving it from the relays" },
+                        "receipt_kind": { "$ref": "#/components/schemas/ReceiptKind" },
+                        "anchor_policy": { "$ref": "#/components/schemas/AnchorPolicy" }
+                    }
+                },
+                "ProofStatus": {
+                    "type": "object",
+                    "required": ["blob_id", "state"],
+                    "properties": {
+                        "blob_id": { "type": "string" },
+                        "state": { "$ref": "#/components/schemas/ProofState" },
+                        "proof": { "type": "string", "nullable": true, "description": "Hex encoded ABI proof tuple (seal, image id, journal). The seal is the JSON encoded receipt for composite and succinct proofs" },
+                        "receipt_kind": { "$ref": "#/components/schemas/ReceiptKind" },
+                        "fake": { "type": "boolean", "description": "Set if the proof is a fake dev mode proof, which doesn't verify onchain" },
+                        "anchor_policy": { "$ref": "#/components/schemas/AnchorPolicy" },
+                        "anchor_block": { "type": "integer", "description": "Block the proof is anchored to, only present once the guest input is built" },
+                        "expiry_block": { "type": "integer", "description": "Last block the proof can be verified onchain at, only present once the guest input is built" },
+                        "freshness": { "type": "string", "enum": ["fresh", "expiring", "expired"], "description": "Freshness of a proven proof against the latest block" },
+                        "consumed": { "type": "boolean", "description": "Set if the proof was marked as consumed, so it's not re-proven when it expires" },
+                        "reorgs": { "type": "integer", "description": "Number of times the proof was invalidated by a reorg of its anchor block and queued again" },
+                        "stats": {
+                            "type": "object",
+                            "description": "Statistics of the proof generation, only present once the proof is generated",
+                            "properties": {
+                                "payload_size": { "type": "integer" },
+                                "proving_secs": { "type": "number" },
+                                "execution": {
+                                    "type": "object",
+                                    "nullable": true,
+                                    "properties": {
+                                        "total_cycles": { "type": "integer" },
+                                        "user_cycles": { "type": "integer" },
+                                        "paging_cycles": { "type": "integer" },
+                                        "segments": { "type": "integer" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                "ServiceInfo": {
+                    "type": "object",
+                    "properties": {
+                        "version": { "type": "string" },
+                        "image_id": { "type": "string", "description": "Hex encoded guest image ID" },
+                        "aggregator_image_id": { "type": "string", "description": "Hex encoded aggregation guest image ID" },
+                        "risc0_version": { "type": "string" },
+                        "verifier_parameters": { "type": "string", "description": "Hex encoded Groth16 receipt verifier parameters digest" },
+                        "verifier_selector": { "type": "string", "description": "Hex encoded selector prepended to the seal" },
+                        "chain": {
+                            "type": "object",
+                            "properties": { "name": { "type": "string" }, "chain_id": { "type": "integer" } }
+                        },
+                        "cert_verifier_router_addr": { "type": "string" },
+                        "payload_form": { "type": "string", "enum": ["coeff", "eval"] },
+                        "anchor_policy": { "$ref": "#/components/schemas/AnchorPolicy" },
+                        "env_commitment": { "type": "string", "enum": ["block", "beacon"], "description": "Whether the steel environment commits to the anchor block hash or its beacon block root" },
+                        "blob_version": { "type": "integer" },
+                        "srs": {
+                            "type": "object",
+                            "properties": { "path": { "type": "string" }, "order": { "type": "integer" }, "points_loaded": { "type": "integer" } }
+                        },
+                        "dev_mode": { "type": "boolean", "description": "Whether fake dev mode proofs are generated and served" }
+                    }
+                },
+                "Error": {
+                    "type": "object",
+                    "required": ["error"],
+                    "properties": { "error": { "type": "string" } }
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,259 @@
+use std::{sync::Arc, time::Duration};
+
+use client::error::{PROOF_NOT_FOUND_ERROR, PROOF_REQUEST_INVALID_STATE_ERROR};
+use client::types::{
+    AggregateProofsParams, AggregationIdParams, GenerateProofParams, ListProofsParams,
+    WaitForProofParams,
+};
+use jsonrpc_core::{ErrorCode, IoHandler, Params};
+
+use crate::admin::WorkerControl;
+use crate::service::{ProvingService, ServiceError};
+
+fn server_error(code: i64, err: ServiceError) -> jsonrpc_core::Error {
+    jsonrpc_core::Error {
+        code: ErrorCode::ServerError(code),
+        message: err.to_string(),
+        data: None,
+    }
+}
+
+impl From<ServiceError> for jsonrpc_core::Error {
+    fn from(err: ServiceError) -> Self {
+        match err {
+            // Failed and already submitted requests keep the codes the sequencer integration
+            // was first given for them, they are told apart by their message
+            ServiceError::InvalidParams(_)
+            | ServiceError::Failed
+            | ServiceError::AlreadySubmitted => {
+                jsonrpc_core::Error::invalid_params(err.to_string())
+            }
+            ServiceError::Queued => server_error(PROOF_NOT_FOUND_ERROR, err),
+            ServiceError::NotFound | ServiceError::AggregationNotFound => jsonrpc_core::Error {
+                code: ErrorCode::InternalError,
+                message: err.to_string(),
+                data: None,
+            },
+            ServiceError::InvalidState(_) => server_error(PROOF_REQUEST_INVALID_STATE_ERROR, err),
+            ServiceError::Internal => jsonrpc_core::Error::internal_error(),
+        }
+    }
+}
+
+fn to_value<T: serde::Serialize>(value: T) -> Result<jsonrpc_core::Value, jsonrpc_core::Error> {
+    serde_json::to_value(value).map_err(|_| jsonrpc_core::Error::internal_error())
+}
+
+fn parse_generate_proof_params(params: Params) -> Result<GenerateProofParams, jsonrpc_core::Error> {
+    params.parse().map_err(|_| {
+        jsonrpc_core::Error::invalid_params(
+            "Expected a string parameter 'blob_id' and optional string parameters 'eigenda_cert', 'payload', 'receipt_kind' (composite, succinct or groth16) and 'anchor_policy' (latest, latest-<confirmations>, <block number> or reference)",
+        )
+    })
+}
+
+fn parse_blob_id(params: Params) -> Result<String, jsonrpc_core::Error> {
+    let parsed: GenerateProofParams = params.parse().map_err(|_| {
+        jsonrpc_core::Error::invalid_params("Expected a single string parameter 'blob_id'")
+    })?;
+    Ok(parsed.blob_id)
+}
+
+/// Builds the JSON RPC handler exposing the proving service methods.
+pub fn build_rpc_handler(service: Arc<ProvingService>) -> IoHandler {
+    let mut io = IoHandler::new();
+
+    let svc = service.clone();
+    io.add_method("generate_proof", move |params: Params| {
+        let service = svc.clone();
+        async move {
+            let params = parse_generate_proof_params(params)?;
+            let status = service.generate_proof(params).await?;
+            Ok(jsonrpc_core::Value::String(format!(
+                "Generating Proof for {}",
+                status.blob_id
+            )))
+        }
+    });
+
+    let svc = service.clone();
+    io.add_method("dry_run", move |params: Params| {
+        let service = svc.clone();
+        async move {
+            let params = parse_generate_proof_params(params)?;
+            let result = service.dry_run(params).await?;
+            to_value(result)
+        }
+    });
+
+    let svc = service.clone();
+    io.add_method("get_proof", move |params: Params| {
+        let service = svc.clone();
+        async move {
+            let blob_id = parse_blob_id(params)?;
+            let proof = service.get_proof(blob_id).await?;
+            Ok(jsonrpc_core::Value::String(proof))
+        }
+    });
+
+    let svc = service.clone();
+    io.add_method("get_proof_status", move |params: Params| {
+        let service = svc.clone();
+        async move {
+            let blob_id = parse_blob_id(params)?;
+            let status = service.get_proof_status(blob_id).await?;
+            to_value(status)
+        }
+    });
+
+    let svc = service.clone();
+    io.add_method("wait_for_proof", move |params: Params| {
+        let service = svc.clone();
+        async move {
+            let parsed: WaitForProofParams = params.parse().map_err(|_| {
+                jsonrpc_core::Error::invalid_params(
+                    "Expected a string parameter 'blob_id' and an optional integer parameter 'timeout_secs'",
+                )
+            })?;
+            let status = service
+                .wait_for_proof(parsed.blob_id, parsed.timeout_secs.map(Duration::from_secs))
+                .await?;
+            to_value(status)
+        }
+    });
+
+    let svc = service.clone();
+    io.add_method("get_service_info", move |_params: Params| {
+        let service = svc.clone();
+        async move { to_value(service.get_service_info()) }
+    });
+
+    let svc = service.clone();
+    io.add_method("list_proofs", move |params: Params| {
+        let service = svc.clone();
+        async move {
+            let parsed: ListProofsParams = match params {
+                Params::None => ListProofsParams::default(),
+                params => params.parse().map_err(|_| {
+                    jsonrpc_core::Error::invalid_params(
+                        "Expected an optional string parameter 'state'",
+                    )
+                })?,
+            };
+            let proofs = service.list_proofs(parsed.state).await?;
+            to_value(proofs)
+        }
+    });
+
+    let svc = service.clone();
+    io.add_method("cancel_proof", move |params: Params| {
+        let service = svc.clone();
+        async move {
+            let blob_id = parse_blob_id(params)?;
+            let status = service.cancel_proof(blob_id).await?;
+            to_value(status)
+        }
+    });
+
+    let svc = service.clone();
+    io.add_method("retry_proof", move |params: Params| {
+        let service = svc.clone();
+        async move {
+            let blob_id = parse_blob_id(params)?;
+            let status = service.retry_proof(blob_id).await?;
+            to_value(status)
+        }
+    });
+
+    let svc = service.clone();
+    io.add_method("recompress_proof", move |params: Params| {
+        let service = svc.clone();
+        async move {
+            let blob_id = parse_blob_id(params)?;
+            let status = service.recompress_proof(blob_id).await?;
+            to_value(status)
+        }
+    });
+
+    let svc = service.clone();
+    io.add_method("mark_proof_consumed", move |params: Params| {
+        let service = svc.clone();
+        async move {
+            let blob_id = parse_blob_id(params)?;
+            let status = service.mark_proof_consumed(blob_id).await?;
+            to_value(status)
+        }
+    });
+
+    let svc = service.clone();
+    io.add_method("export_guest_input", move |params: Params| {
+        let service = svc.clone();
+        async move {
+            let blob_id = parse_blob_id(params)?;
+            let bundle = service.export_guest_input(blob_id).await?;
+            to_value(bundle)
+        }
+    });
+
+    let svc = service.clone();
+    io.add_method("aggregate_proofs", move |params: Params| {
+        let service = svc.clone();
+        async move {
+            let parsed: AggregateProofsParams = params.parse().map_err(|_| {
+                jsonrpc_core::Error::invalid_params("Expected a string array parameter 'blob_ids'")
+            })?;
+            let status = service.aggregate_proofs(parsed.blob_ids).await?;
+            to_value(status)
+        }
+    });
+
+    let svc = service.clone();
+    io.add_method("get_aggregation_status", move |params: Params| {
+        let service = svc.clone();
+        async move {
+            let parsed: AggregationIdParams = params.parse().map_err(|_| {
+                jsonrpc_core::Error::invalid_params(
+                    "Expected an integer parameter 'aggregation_id'",
+                )
+            })?;
+            let status = service
+                .get_aggregation_status(parsed.aggregation_id)
+                .await?;
+            to_value(status)
+        }
+    });
+
+    io
+}
+
+/// Builds the JSON RPC handler of the admin methods, that control the proof generation worker.
+/// It is served on its own address, so that access to it can be restricted.
+pub fn build_admin_rpc_handler(control: Arc<WorkerControl>) -> IoHandler {
+    let mut io = IoHandler::new();
+
+    let ctl = control.clone();
+    io.add_method("admin_pause", move |_params: Params| {
+        let control = ctl.clone();
+        async move { to_value(control.pause().await?) }
+    });
+
+    let ctl = control.clone();
+    io.add_method("admin_resume", move |_params: Params| {
+        let control = ctl.clone();
+        async move { to_value(control.resume().await?) }
+    });
+
+    let ctl = control.clone();
+    io.add_method("admin_drain", move |_params: Params| {
+        let control = ctl.clone();
+        async move { to_value(control.drain().await?) }
+    });
+
+    let ctl = control.clone();
+    io.add_method("admin_worker_activity", move |_params: Params| {
+        let control = ctl.clone();
+        async move { to_value(control.activity()) }
+    });
+
+    io
+}
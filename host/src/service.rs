@@ -0,0 +1,665 @@
+use std::{collections::HashSet, fmt, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use client::error::{
+    AGGREGATION_NOT_FOUND_MESSAGE, ALREADY_SUBMITTED_MESSAGE, PROOF_GENERATION_FAILED_MESSAGE,
+    PROOF_REQUEST_NOT_FOUND_MESSAGE,
+};
+use rust_eigenda_v2_client::{core::BlobKey, payload_disperser::PayloadDisperser};
+use rust_eigenda_v2_common::EigenDACert;
+use sqlx::{Pool, Postgres};
+use tokio::{
+    sync::{broadcast::error::RecvError, Mutex},
+    time::Instant,
+};
+
+use crate::db::{
+    cancel_blob_proof_request, mark_blob_proof_consumed, proof_request_exists,
+    recompress_blob_proof, retrieve_aggregation, retrieve_blob_id_proof, retrieve_blob_proofs,
+    retrieve_proof_request, retry_blob_proof_request, store_aggregation_request,
+    store_blob_proof_request, BlobProofRecord, PendingProof,
+};
+use crate::guest_caller::GuestInputBundle;
+use crate::metrics::{PROOF_GEN_REQ_COUNTER, PROOF_RET_REQ_COUNTER};
+use crate::notifier::ProofNotifier;
+use crate::worker::ProofWorker;
+
+/// Maximum number of proof requests returned when listing them.
+pub const MAX_LISTED_PROOFS: i64 = 1000;
+
+/// Time `wait_for_proof` waits if the caller doesn't give a timeout.
+pub const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Maximum time `wait_for_proof` holds a request.
+pub const MAX_WAIT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Maximum number of blob proofs aggregated into one.
+pub const MAX_AGGREGATED_PROOFS: usize = 256;
+
+pub use client::types::{
+    AggregationStatus, AnchorPolicy, DryRunResult, GenerateProofParams, ProofFreshness, ProofState,
+    ProofStatus, ReceiptKind, ServiceInfo,
+};
+
+/// Decodes a hex encoded ABI `EigenDACert`, as received by `generate_proof`.
+pub fn decode_eigenda_cert(encoded: &str) -> anyhow::Result<EigenDACert> {
+    let bytes = hex::decode(encoded.trim_start_matches("0x"))?;
+    EigenDACert::from_abi_encoded(&bytes)
+        .map_err(|e| anyhow::anyhow!("Failed decoding EigenDA cert: {:?}", e))
+}
+
+/// Source of the EigenDA certs of the blobs submitted without one.
+#[async_trait]
+pub trait CertRetriever: Send + Sync {
+    /// Returns the EigenDA cert of the blob, `None` if it's not available yet.
+    async fn get_cert(&self, blob_key: &BlobKey) -> anyhow::Result<Option<EigenDACert>>;
+}
+
+#[async_trait]
+impl CertRetriever for PayloadDisperser {
+    async fn get_cert(&self, blob_key: &BlobKey) -> anyhow::Result<Option<EigenDACert>> {
+        Ok(PayloadDisperser::get_cert(self, blob_key).await?)
+    }
+}
+
+/// Errors returned by the proving service handlers.
+/// Each API (JSON-RPC, REST) maps them to its own error representation.
+#[derive(Debug)]
+pub enum ServiceError {
+    /// The request parameters are not valid.
+    InvalidParams(String),
+    /// A proof request for the blob was already submitted.
+    AlreadySubmitted,
+    /// No proof request exists for the blob.
+    NotFound,
+    /// The proof request exists but the proof is not generated yet.
+    Queued,
+    /// The proof generation for the blob failed.
+    Failed,
+    /// The proof request is not in a state that allows the operation.
+    InvalidState(String),
+    /// No aggregation request exists with the given ID.
+    AggregationNotFound,
+    /// Unexpected error while handling the request (e.g. database failure).
+    Internal,
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceError::InvalidParams(message) => write!(f, "{}", message),
+            ServiceError::AlreadySubmitted => write!(f, "{}", ALREADY_SUBMITTED_MESSAGE),
+            ServiceError::NotFound => write!(f, "{}", PROOF_REQUEST_NOT_FOUND_MESSAGE),
+            ServiceError::Queued => write!(f, "Proof not found (still queued)"),
+            ServiceError::Failed => write!(f, "{}", PROOF_GENERATION_FAILED_MESSAGE),
+            ServiceError::InvalidState(message) => write!(f, "{}", message),
+            ServiceError::AggregationNotFound => write!(f, "{}", AGGREGATION_NOT_FOUND_MESSAGE),
+            ServiceError::Internal => write!(f, "Internal error"),
+        }
+    }
+}
+
+impl std::error::Error for ServiceError {}
+
+/// Request handlers shared by every API exposed by the proving service.
+pub struct ProvingService {
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    cert_retriever: Arc<dyn CertRetriever>,
+    service_info: ServiceInfo,
+    notifier: ProofNotifier,
+    worker: Arc<ProofWorker>,
+    expiry_margin_blocks: u64,
+}
+
+impl ProvingService {
+    pub fn new(
+        db_pool: Arc<Mutex<Pool<Postgres>>>,
+        cert_retriever: Arc<dyn CertRetriever>,
+        service_info: ServiceInfo,
+        notifier: ProofNotifier,
+        worker: Arc<ProofWorker>,
+        expiry_margin_blocks: u64,
+    ) -> Self {
+        Self {
+            db_pool,
+            cert_retriever,
+            service_info,
+            notifier,
+            worker,
+            expiry_margin_blocks,
+        }
+    }
+
+    /// Returns the configuration the proofs are generated with.
+    pub fn get_service_info(&self) -> ServiceInfo {
+        self.service_info.clone()
+    }
+
+    /// Validates the blob ID (and EigenDA cert and payload, if given) and queues a proof request for it.
+    pub async fn generate_proof(
+        &self,
+        params: GenerateProofParams,
+    ) -> Result<ProofStatus, ServiceError> {
+        PROOF_GEN_REQ_COUNTER.inc();
+        tracing::info!(
+            "Received request to generate proof for Blob Id {}",
+            params.blob_id
+        );
+        self.validate_proof_request(&params).await?;
+        let GenerateProofParams {
+            blob_id,
+            eigenda_cert,
+            payload,
+            receipt_kind,
+            anchor_policy,
+        } = params;
+        let receipt_kind = receipt_kind.unwrap_or_default();
+        let anchor_policy = anchor_policy.unwrap_or(self.service_info.anchor_policy);
+
+        if proof_request_exists(self.db_pool.clone(), blob_id.clone())
+            .await
+            .map_err(|_| {
+                tracing::error!(
+                    "Failed checking if Blob Id {} already has a proof request",
+                    blob_id
+                );
+                ServiceError::Internal
+            })?
+        {
+            return Err(ServiceError::AlreadySubmitted);
+        }
+
+        // Persist request in database
+        store_blob_proof_request(
+            self.db_pool.clone(),
+            blob_id.clone(),
+            eigenda_cert,
+            payload,
+            receipt_kind,
+            anchor_policy,
+        )
+        .await
+        .map_err(|_| {
+            tracing::error!("Failed sending Blob Id {} to prover thread", blob_id);
+            ServiceError::Internal
+        })?;
+
+        Ok(ProofStatus {
+            blob_id,
+            state: ProofState::Queued,
+            proof: None,
+            receipt_kind,
+            fake: false,
+            anchor_policy,
+            anchor_block: None,
+            expiry_block: None,
+            freshness: None,
+            consumed: false,
+            reorgs: 0,
+            stats: None,
+        })
+    }
+
+    /// Checks the blob ID is valid, and that the EigenDA cert and payload, if given, belong to it.
+    async fn validate_proof_request(
+        &self,
+        params: &GenerateProofParams,
+    ) -> Result<(), ServiceError> {
+        let blob_key = BlobKey::from_hex(&params.blob_id)
+            .map_err(|_| ServiceError::InvalidParams("Invalid blob ID".to_string()))?;
+        if let Some(payload) = &params.payload {
+            if params.eigenda_cert.is_none() {
+                return Err(ServiceError::InvalidParams(
+                    "A payload can only be given along with its EigenDA cert".to_string(),
+                ));
+            }
+            if hex::decode(payload.trim_start_matches("0x")).is_err() {
+                return Err(ServiceError::InvalidParams(
+                    "Invalid payload, expected a hex string".to_string(),
+                ));
+            }
+        }
+        match &params.eigenda_cert {
+            Some(eigenda_cert) => {
+                let eigenda_cert = decode_eigenda_cert(eigenda_cert).map_err(|e| {
+                    ServiceError::InvalidParams(format!("Invalid EigenDA cert: {}", e))
+                })?;
+                let cert_blob_key = eigenda_cert.compute_blob_key().map_err(|e| {
+                    ServiceError::InvalidParams(format!("Invalid EigenDA cert: {:?}", e))
+                })?;
+                if cert_blob_key.to_hex() != blob_key.to_hex() {
+                    return Err(ServiceError::InvalidParams(
+                        "EigenDA cert does not belong to the Blob ID".to_string(),
+                    ));
+                }
+            }
+            None => {
+                if self.cert_retriever.get_cert(&blob_key).await.is_err() {
+                    return Err(ServiceError::InvalidParams(
+                        "Blob ID not found in EigenDA".to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates the request like [`ProvingService::generate_proof`], and runs the guest for it in
+    /// the executor, without proving it nor queuing a proof request.
+    pub async fn dry_run(&self, params: GenerateProofParams) -> Result<DryRunResult, ServiceError> {
+        tracing::info!("Received request to dry run Blob Id {}", params.blob_id);
+        self.validate_proof_request(&params).await?;
+        let result = self
+            .worker
+            .dry_run(PendingProof {
+                blob_id: params.blob_id,
+                eigenda_cert: params.eigenda_cert,
+                payload: params.payload,
+                receipt: None,
+                receipt_kind: params.receipt_kind.unwrap_or_default(),
+                anchor_policy: params
+                    .anchor_policy
+                    .unwrap_or(self.service_info.anchor_policy),
+                guest_input: None,
+                reorgs: 0,
+            })
+            .await;
+        match &result.error {
+            None => tracing::info!("Dry run for Blob Id {} passed", result.blob_id),
+            Some(error) => {
+                tracing::info!("Dry run for Blob Id {} failed: {}", result.blob_id, error)
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns the current status of the proof request for the blob ID.
+    pub async fn get_proof_status(&self, blob_id: String) -> Result<ProofStatus, ServiceError> {
+        PROOF_RET_REQ_COUNTER.inc();
+        tracing::info!("Received request to get proof for Blob Id {}", blob_id);
+        let mut status = self.fetch_proof_status(blob_id).await?;
+        self.set_freshness(std::slice::from_mut(&mut status)).await;
+        Ok(status)
+    }
+
+    /// Sets the freshness of the proven statuses against the latest block. It's left unset
+    /// if the latest block can't be retrieved.
+    async fn set_freshness(&self, statuses: &mut [ProofStatus]) {
+        if !statuses
+            .iter()
+            .any(|status| status.state == ProofState::Proven && status.expiry_block.is_some())
+        {
+            return;
+        }
+        let latest_block = match self.worker.latest_block_number().await {
+            Ok(latest_block) => latest_block,
+            Err(e) => {
+                tracing::warn!("Failed retrieving latest block number: {}", e);
+                return;
+            }
+        };
+        for status in statuses {
+            if status.state != ProofState::Proven {
+                continue;
+            }
+            status.freshness = status.expiry_block.map(|expiry_block| {
+                ProofFreshness::at(expiry_block, latest_block, self.expiry_margin_blocks)
+            });
+        }
+    }
+
+    /// Same as [`ProvingService::get_proof_status`], without accounting it as a
+    /// client request. Used by APIs that poll the status on behalf of the client.
+    pub(crate) async fn fetch_proof_status(
+        &self,
+        blob_id: String,
+    ) -> Result<ProofStatus, ServiceError> {
+        match retrieve_blob_id_proof(self.db_pool.clone(), blob_id.clone()).await {
+            Err(e) => {
+                tracing::error!("Failed retrieving proof for Blob ID {}: {}", blob_id, e);
+                Err(ServiceError::Internal)
+            }
+            Ok(None) => {
+                tracing::debug!("Proof for Blob ID {} not found", blob_id);
+                Err(ServiceError::NotFound)
+            }
+            Ok(Some(record)) => Ok(self.served_status(record.into_status())),
+        }
+    }
+
+    /// Every status returned by the service goes through here: fake dev mode proofs are only
+    /// served in dev mode, otherwise they are stripped, keeping the `fake` flag to tell why.
+    fn served_status(&self, mut status: ProofStatus) -> ProofStatus {
+        if status.fake && !self.service_info.dev_mode {
+            status.proof = None;
+        }
+        status
+    }
+
+    /// Returns the generated proof for the blob ID, or an error describing
+    /// why it is not available. Fake dev mode proofs are only served in dev mode.
+    pub async fn get_proof(&self, blob_id: String) -> Result<String, ServiceError> {
+        let status = self.get_proof_status(blob_id).await?;
+        match (status.state, status.proof) {
+            (ProofState::Failed, _) => Err(ServiceError::Failed),
+            (ProofState::Cancelled, _) => Err(ServiceError::InvalidState(
+                "Proof request for Blob ID was cancelled".to_string(),
+            )),
+            (ProofState::Proven, None) if status.fake => Err(ServiceError::InvalidState(
+                "Proof for Blob ID is a fake dev mode proof, and dev mode is disabled".to_string(),
+            )),
+            (_, Some(proof)) => Ok(proof),
+            (_, None) => {
+                tracing::debug!(
+                    "Proof for Blob ID {} not found (still queued)",
+                    status.blob_id
+                );
+                Err(ServiceError::Queued)
+            }
+        }
+    }
+
+    /// Waits until the proof request for the blob ID leaves the queue (it is proven, fails or
+    /// is cancelled) or the timeout elapses, and returns its status.
+    /// Driven by the notifications of the proof generation thread, the database is only
+    /// queried when starting, on timeout, or if notifications were missed.
+    pub async fn wait_for_proof(
+        &self,
+        blob_id: String,
+        timeout: Option<Duration>,
+    ) -> Result<ProofStatus, ServiceError> {
+        PROOF_RET_REQ_COUNTER.inc();
+        tracing::info!("Received request to wait for proof for Blob Id {}", blob_id);
+        let timeout = timeout
+            .unwrap_or(DEFAULT_WAIT_TIMEOUT)
+            .min(MAX_WAIT_TIMEOUT);
+        let deadline = Instant::now() + timeout;
+        self.await_proof_status(blob_id, Some(deadline)).await
+    }
+
+    /// Same as [`ProvingService::wait_for_proof`], without accounting it as a client
+    /// request. Waits until the given deadline, or forever if there is none.
+    pub(crate) async fn await_proof_status(
+        &self,
+        blob_id: String,
+        deadline: Option<Instant>,
+    ) -> Result<ProofStatus, ServiceError> {
+        // Subscribe before reading the status, so no update is missed in between
+        let mut notifications = self.notifier.subscribe();
+        let status = self.fetch_proof_status(blob_id.clone()).await?;
+        if status.state != ProofState::Queued {
+            return Ok(status);
+        }
+
+        loop {
+            let notification = match deadline {
+                Some(deadline) => tokio::time::timeout_at(deadline, notifications.recv()).await,
+                None => Ok(notifications.recv().await),
+            };
+            match notification {
+                // Queued again by a monitor, it is still waiting
+                Ok(Ok(status))
+                    if status.blob_id == blob_id && status.state != ProofState::Queued =>
+                {
+                    return Ok(self.served_status(status))
+                }
+                Ok(Ok(_)) => continue,
+                Ok(Err(RecvError::Lagged(_))) => {
+                    let status = self.fetch_proof_status(blob_id.clone()).await?;
+                    if status.state != ProofState::Queued {
+                        return Ok(status);
+                    }
+                }
+                // Nothing will be notified anymore, or the timeout elapsed
+                Ok(Err(RecvError::Closed)) | Err(_) => {
+                    return self.fetch_proof_status(blob_id).await
+                }
+            }
+        }
+    }
+
+    /// Lists the proof requests in the given state (or all of them).
+    pub async fn list_proofs(
+        &self,
+        state: Option<ProofState>,
+    ) -> Result<Vec<ProofStatus>, ServiceError> {
+        let records = retrieve_blob_proofs(self.db_pool.clone(), state, MAX_LISTED_PROOFS)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed listing proof requests: {}", e);
+                ServiceError::Internal
+            })?;
+
+        let mut statuses: Vec<ProofStatus> = records
+            .into_iter()
+            .map(|record| self.served_status(record.into_status()))
+            .collect();
+        self.set_freshness(&mut statuses).await;
+        Ok(statuses)
+    }
+
+    /// Marks the proof of a proven request as consumed (e.g. committed onchain), so that
+    /// it's not re-proven when it expires.
+    pub async fn mark_proof_consumed(&self, blob_id: String) -> Result<ProofStatus, ServiceError> {
+        tracing::info!(
+            "Received request to mark proof for Blob Id {} as consumed",
+            blob_id
+        );
+        let marked = mark_blob_proof_consumed(self.db_pool.clone(), blob_id.clone())
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    "Failed marking proof for Blob ID {} as consumed: {}",
+                    blob_id,
+                    e
+                );
+                ServiceError::Internal
+            })?;
+        if !marked {
+            let status = self.fetch_proof_status(blob_id).await?;
+            return Err(ServiceError::InvalidState(format!(
+                "Only proven requests can be marked as consumed, proof request is {}",
+                status.state
+            )));
+        }
+        self.fetch_proof_status(blob_id).await
+    }
+
+    /// Cancels a queued proof request, so the prover thread skips it.
+    pub async fn cancel_proof(&self, blob_id: String) -> Result<ProofStatus, ServiceError> {
+        tracing::info!("Received request to cancel proof for Blob Id {}", blob_id);
+        let cancelled = cancel_blob_proof_request(self.db_pool.clone(), blob_id.clone())
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed cancelling proof for Blob ID {}: {}", blob_id, e);
+                ServiceError::Internal
+            })?;
+        if !cancelled {
+            let status = self.fetch_proof_status(blob_id).await?;
+            return Err(ServiceError::InvalidState(format!(
+                "Only queued proof requests can be cancelled, proof request is {}",
+                status.state
+            )));
+        }
+        let status = self.fetch_proof_status(blob_id).await?;
+        self.notifier.notify(status.clone());
+        Ok(status)
+    }
+
+    /// Puts a failed or cancelled proof request back in the queue.
+    /// Proven requests with a fake dev mode proof can be retried too, to get a real proof.
+    /// If the guest was already proven, only the compression of its receipt runs again.
+    pub async fn retry_proof(&self, blob_id: String) -> Result<ProofStatus, ServiceError> {
+        tracing::info!("Received request to retry proof for Blob Id {}", blob_id);
+        let retried = retry_blob_proof_request(self.db_pool.clone(), blob_id.clone())
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed retrying proof for Blob ID {}: {}", blob_id, e);
+                ServiceError::Internal
+            })?;
+        if !retried {
+            let status = self.fetch_proof_status(blob_id).await?;
+            return Err(ServiceError::InvalidState(format!(
+                "Only failed, cancelled or fake proof requests can be retried, proof request is {}",
+                status.state
+            )));
+        }
+        let status = self.fetch_proof_status(blob_id).await?;
+        self.notifier.notify(status.clone());
+        Ok(status)
+    }
+
+    /// Puts a proven request back in the queue to compress its stored succinct receipt into
+    /// a new Groth16 proof, without proving the guest again. Meant to be used after the
+    /// Groth16 verifier parameters change.
+    pub async fn recompress_proof(&self, blob_id: String) -> Result<ProofStatus, ServiceError> {
+        tracing::info!(
+            "Received request to recompress proof for Blob Id {}",
+            blob_id
+        );
+        let recompressed = recompress_blob_proof(self.db_pool.clone(), blob_id.clone())
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed recompressing proof for Blob ID {}: {}", blob_id, e);
+                ServiceError::Internal
+            })?;
+        if !recompressed {
+            let status = self.fetch_proof_status(blob_id).await?;
+            return Err(ServiceError::InvalidState(format!(
+                "Only proven Groth16 requests with a stored receipt can be recompressed, proof request is {}",
+                status.state
+            )));
+        }
+        let status = self.fetch_proof_status(blob_id).await?;
+        self.notifier.notify(status.clone());
+        Ok(status)
+    }
+
+    /// Returns the self-contained guest input of the proof request, to execute or prove the
+    /// guest offline with the `replay` binary.
+    pub async fn export_guest_input(
+        &self,
+        blob_id: String,
+    ) -> Result<GuestInputBundle, ServiceError> {
+        tracing::info!(
+            "Received request to export guest input for Blob Id {}",
+            blob_id
+        );
+        let proof_request = retrieve_proof_request(self.db_pool.clone(), blob_id.clone())
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    "Failed retrieving proof request for Blob ID {}: {}",
+                    blob_id,
+                    e
+                );
+                ServiceError::Internal
+            })?
+            .ok_or(ServiceError::NotFound)?;
+        self.worker
+            .export_guest_input(&proof_request)
+            .await
+            .map_err(|e| {
+                ServiceError::InvalidState(format!("Failed building the guest input: {}", e))
+            })
+    }
+
+    /// Queues a request to aggregate the proofs of the given blobs into a single proof,
+    /// verifiable onchain with one Groth16 verification. Every blob must be proven into a
+    /// succinct or groth16 proof, and fake dev mode proofs can only be aggregated in dev mode.
+    pub async fn aggregate_proofs(
+        &self,
+        blob_ids: Vec<String>,
+    ) -> Result<AggregationStatus, ServiceError> {
+        tracing::info!(
+            "Received request to aggregate the proofs of {} blobs",
+            blob_ids.len()
+        );
+        if blob_ids.is_empty() {
+            return Err(ServiceError::InvalidParams(
+                "At least one Blob ID is required".to_string(),
+            ));
+        }
+        if blob_ids.len() > MAX_AGGREGATED_PROOFS {
+            return Err(ServiceError::InvalidParams(format!(
+                "At most {} proofs can be aggregated",
+                MAX_AGGREGATED_PROOFS
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        for blob_id in &blob_ids {
+            if !seen.insert(blob_id) {
+                return Err(ServiceError::InvalidParams(format!(
+                    "Blob ID {} is repeated",
+                    blob_id
+                )));
+            }
+            let status = match self.fetch_proof_status(blob_id.clone()).await {
+                Err(ServiceError::NotFound) => {
+                    return Err(ServiceError::InvalidParams(format!(
+                        "Proof request for Blob ID {} not found",
+                        blob_id
+                    )))
+                }
+                status => status?,
+            };
+            if status.state != ProofState::Proven {
+                return Err(ServiceError::InvalidState(format!(
+                    "Only proven requests can be aggregated, proof request for Blob ID {} is {}",
+                    blob_id, status.state
+                )));
+            }
+            if status.fake && !self.service_info.dev_mode {
+                return Err(ServiceError::InvalidState(format!(
+                    "Proof for Blob ID {} is a fake dev mode proof, and dev mode is disabled",
+                    blob_id
+                )));
+            }
+            // The aggregation guest verifies succinct receipts, which are stored for both
+            // succinct and groth16 requests
+            if status.receipt_kind == ReceiptKind::Composite {
+                return Err(ServiceError::InvalidState(format!(
+                    "Only succinct or groth16 proofs can be aggregated, proof for Blob ID {} is composite",
+                    blob_id
+                )));
+            }
+        }
+
+        let id = store_aggregation_request(self.db_pool.clone(), blob_ids)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed storing aggregation request: {}", e);
+                ServiceError::Internal
+            })?;
+        tracing::info!("Queued aggregation request {}", id);
+        self.fetch_aggregation_status(id).await
+    }
+
+    /// Returns the status of the aggregation request, including its proof once generated.
+    pub async fn get_aggregation_status(
+        &self,
+        aggregation_id: u64,
+    ) -> Result<AggregationStatus, ServiceError> {
+        let id = i32::try_from(aggregation_id).map_err(|_| ServiceError::AggregationNotFound)?;
+        self.fetch_aggregation_status(id).await
+    }
+
+    async fn fetch_aggregation_status(&self, id: i32) -> Result<AggregationStatus, ServiceError> {
+        match retrieve_aggregation(self.db_pool.clone(), id).await {
+            Err(e) => {
+                tracing::error!("Failed retrieving aggregation {}: {}", id, e);
+                Err(ServiceError::Internal)
+            }
+            Ok(None) => Err(ServiceError::AggregationNotFound),
+            Ok(Some(record)) => {
+                let mut status = record.into_status();
+                // Same as the blob proofs, see `served_status`
+                if status.fake && !self.service_info.dev_mode {
+                    status.proof = None;
+                }
+                Ok(status)
+            }
+        }
+    }
+}
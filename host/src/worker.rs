@@ -0,0 +1,777 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use alloy_primitives::Address;
+use anyhow::Result;
+use client::proof::{is_fake_proof, FAKE_PROOF_SELECTOR};
+use common::chain::Chain;
+use ethabi::Token;
+use methods::{AGGREGATOR_ELF, GUEST_ELF, GUEST_ID};
+use risc0_zkvm::{compute_image_id, sha::Digestible, InnerReceipt, Receipt};
+use rust_eigenda_v2_client::{
+    core::BlobKey,
+    relay_client::{RelayClient, RelayClientConfig},
+    relay_payload_retriever::{RelayPayloadRetriever, RelayPayloadRetrieverConfig, SRSConfig},
+    utils::SecretUrl,
+};
+use rust_eigenda_v2_common::{EigenDACert, Payload, PayloadForm};
+use rust_kzg_bn254_prover::srs::SRS;
+use sqlx::{Pool, Postgres};
+use tokio::sync::{Mutex, OnceCell};
+use url::Url;
+
+use crate::admin::{WorkerControl, WorkerState};
+use crate::chain::EthRpcClient;
+use crate::db::{
+    mark_aggregation_failed, mark_blob_proof_request_failed, retrieve_blob_receipts,
+    retrieve_next_pending_aggregation, retrieve_next_pending_proof, store_aggregation_proof,
+    store_blob_proof, store_blob_receipt, store_guest_input, PendingAggregation, PendingProof,
+};
+use crate::guest_caller::{
+    build_guest_input, execute_guest, run_guest, verify_payload_commitment, AggregationInput,
+    GuestInput, GuestInputBundle, InputMismatch, InputSettings,
+};
+use crate::health::WorkerHeartbeat;
+use crate::metrics::{
+    observe_proof_stats, AGGREGATION_FAILURE_COUNTER, AGGREGATION_SUCCESS_COUNTER,
+    GUEST_INPUT_REUSED_COUNTER, PROOF_COMPRESSION_TIME_HISTOGRAM, PROOF_GEN_FAILURE_COUNTER,
+    PROOF_GEN_SUCCESS_COUNTER, PROOF_GEN_TIME_HISTOGRAM,
+};
+use crate::notifier::ProofNotifier;
+use crate::prover::{ProveOutput, ProverBackend};
+use crate::service::{
+    decode_eigenda_cert, AnchorPolicy, CertRetriever, DryRunResult, ProofStats, ReceiptKind,
+};
+
+pub const SRS_PATH: &str = "resources/g1.point";
+pub const SRS_ORDER: u32 = 268435456;
+pub const SRS_POINTS_TO_LOAD: u32 = 1024 * 1024 * 2 / 32;
+
+/// Configuration needed to connect to the EigenDA relays.
+pub struct RelayConfig {
+    pub relay_client_keys: Vec<u32>,
+    pub relay_registry_address: Address,
+}
+
+/// Proof generation thread: takes pending proof requests from the database and proves them.
+pub struct ProofWorker {
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    cert_retriever: Arc<dyn CertRetriever>,
+    relay_config: RelayConfig,
+    // Connected on first use, so the worker can prove caller supplied payloads
+    // even if the relays are unreachable.
+    retriever: OnceCell<Mutex<RelayPayloadRetriever>>,
+    srs: SRS,
+    rpc_url: Url,
+    cert_verifier_router_addr: Address,
+    payload_form: PayloadForm,
+    chain: Chain,
+    // Beacon API endpoint, if set the steel environments commit to beacon block roots
+    beacon_api_url: Option<Url>,
+    notifier: ProofNotifier,
+    control: Arc<WorkerControl>,
+    prover: Arc<dyn ProverBackend>,
+    heartbeat: WorkerHeartbeat,
+    eth_rpc: EthRpcClient,
+    max_input_age_blocks: u64,
+    expiry_margin_blocks: u64,
+}
+
+impl ProofWorker {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        db_pool: Arc<Mutex<Pool<Postgres>>>,
+        cert_retriever: Arc<dyn CertRetriever>,
+        relay_config: RelayConfig,
+        srs: SRS,
+        rpc_url: Url,
+        cert_verifier_router_addr: Address,
+        payload_form: PayloadForm,
+        chain: Chain,
+        beacon_api_url: Option<Url>,
+        notifier: ProofNotifier,
+        control: Arc<WorkerControl>,
+        prover: Arc<dyn ProverBackend>,
+        max_input_age_blocks: u64,
+        expiry_margin_blocks: u64,
+    ) -> Self {
+        Self {
+            eth_rpc: EthRpcClient::new(rpc_url.clone()),
+            db_pool,
+            cert_retriever,
+            relay_config,
+            retriever: OnceCell::new(),
+            srs,
+            rpc_url,
+            cert_verifier_router_addr,
+            payload_form,
+            chain,
+            beacon_api_url,
+            notifier,
+            control,
+            prover,
+            heartbeat: WorkerHeartbeat::default(),
+            max_input_age_blocks,
+            expiry_margin_blocks,
+        }
+    }
+
+    /// Heartbeat of the proof generation loop.
+    pub fn heartbeat(&self) -> &WorkerHeartbeat {
+        &self.heartbeat
+    }
+
+    /// Number of the latest block of the chain.
+    pub async fn latest_block_number(&self) -> Result<u64> {
+        self.eth_rpc.block_number().await
+    }
+
+    /// Number of SRS points loaded to compute the KZG proofs.
+    pub fn srs_points_loaded(&self) -> usize {
+        self.srs.g1.len()
+    }
+
+    /// Runs the proof generation loop. Only returns if the database can't be updated.
+    pub async fn run(&self) -> Result<()> {
+        loop {
+            self.heartbeat.beat();
+            if self.control.state() != WorkerState::Running {
+                tracing::debug!("Proof generation paused");
+                // Wake up as soon as it's resumed, but keep the loop beating while paused
+                let _ = tokio::time::timeout(
+                    Duration::from_secs(5),
+                    self.control.wait_for_state(WorkerState::Running),
+                )
+                .await;
+                continue;
+            }
+
+            let pending_proof = match retrieve_next_pending_proof(self.db_pool.clone()).await {
+                Ok(Some(pending_proof)) => pending_proof,
+                Ok(None) => {
+                    // Blob proofs go first, aggregations are only proven once the queue is empty
+                    if !self.run_next_aggregation().await? {
+                        tracing::debug!("No pending proofs found");
+                        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                    }
+                    continue;
+                }
+                Err(e) => {
+                    tracing::error!("Error retrieving pending proof: {}", e);
+                    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                    continue;
+                }
+            };
+
+            tracing::info!(
+                "Proof generation thread: retrieved request to prove: {}",
+                pending_proof.blob_id
+            );
+            self.prove_request(pending_proof).await?;
+        }
+    }
+
+    /// Generates the proof of a pending proof request, stores it, or marks the request as
+    /// failed, and notifies its new status. Only fails if the database can't be updated.
+    pub async fn prove_request(&self, pending_proof: PendingProof) -> Result<()> {
+        let blob_id = pending_proof.blob_id.clone();
+        let reorgs = pending_proof.reorgs;
+
+        let timer = PROOF_GEN_TIME_HISTOGRAM
+            .with_label_values(&[&blob_id])
+            .start_timer();
+
+        // Pausing only stops taking new proof requests, the prover runs in a blocking
+        // thread that can't be interrupted, so the current one is always finished
+        self.heartbeat.job_started();
+        self.control.job_started(&blob_id);
+        let result = self.generate_proof(pending_proof).await;
+        self.control.job_finished();
+        self.heartbeat.job_finished();
+
+        match result {
+            Ok(proof) => {
+                let fake = is_fake_proof(&proof);
+                if fake {
+                    tracing::warn!(
+                        "Proof gen thread: generated FAKE dev mode proof for Blob Id {}",
+                        blob_id
+                    );
+                } else {
+                    tracing::info!("Proof gen thread: generated proof for Blob Id {}", blob_id);
+                }
+                // Persist proof in database
+                let stored = store_blob_proof(
+                    self.db_pool.clone(),
+                    blob_id.clone(),
+                    hex::encode(proof),
+                    fake,
+                    reorgs,
+                )
+                .await?;
+                if !stored {
+                    tracing::info!(
+                        "Proof gen thread: Blob Id {} was cancelled or reorged while proving, its proof is discarded",
+                        blob_id
+                    );
+                }
+                PROOF_GEN_SUCCESS_COUNTER.inc();
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Proof gen thread: error generating proof for Blob Id: {}, error: {}",
+                    blob_id,
+                    e
+                );
+                // Mark the proof request as invalid in the database, unless it was cancelled
+                mark_blob_proof_request_failed(self.db_pool.clone(), blob_id.clone()).await?;
+                PROOF_GEN_FAILURE_COUNTER.inc();
+            }
+        };
+        self.notify_status(blob_id).await;
+
+        timer.observe_duration();
+        Ok(())
+    }
+
+    // Receives a pending proof request and generates its proof, in two stages: the guest is
+    // proven into a composite or succinct receipt, as requested, which is persisted, and then
+    // it's compressed into a Groth16 receipt if that's the kind requested.
+    // If the receipt was already persisted (the compression failed and the request was
+    // retried, or it is being recompressed) only the compression runs.
+    // Returns the proof encoded as and ethabi Tuple.
+    async fn generate_proof(&self, pending_proof: PendingProof) -> Result<Vec<u8>> {
+        let receipt = match pending_proof.receipt.as_deref() {
+            Some(receipt) => {
+                tracing::info!(
+                    "Proof gen thread: compressing stored receipt of Blob Id {}",
+                    pending_proof.blob_id
+                );
+                serde_json::from_str(receipt)?
+            }
+            None => self.prove_receipt(&pending_proof).await?,
+        };
+
+        let receipt = if pending_proof.receipt_kind == ReceiptKind::Groth16 {
+            let timer = PROOF_COMPRESSION_TIME_HISTOGRAM.start_timer();
+            let receipt = self.prover.compress(receipt).await?;
+            timer.observe_duration();
+            receipt
+        } else {
+            receipt
+        };
+
+        encode_proof(&receipt, compute_image_id(GUEST_ELF)?)
+    }
+
+    /// Proves the next pending aggregation request, if any, and stores its result.
+    /// Returns whether there was one. Only fails if the database can't be updated.
+    async fn run_next_aggregation(&self) -> Result<bool> {
+        let aggregation = match retrieve_next_pending_aggregation(self.db_pool.clone()).await {
+            Ok(Some(aggregation)) => aggregation,
+            Ok(None) => return Ok(false),
+            Err(e) => {
+                tracing::error!("Error retrieving pending aggregation: {}", e);
+                return Ok(false);
+            }
+        };
+        let id = aggregation.id;
+        tracing::info!(
+            "Proof generation thread: retrieved aggregation {} of {} proofs",
+            id,
+            aggregation.blob_ids.len()
+        );
+
+        self.heartbeat.job_started();
+        self.control.job_started(&format!("aggregation {}", id));
+        let result = self.generate_aggregated_proof(aggregation).await;
+        self.control.job_finished();
+        self.heartbeat.job_finished();
+
+        match result {
+            Ok(proof) => {
+                tracing::info!("Proof gen thread: generated aggregated proof {}", id);
+                let fake = is_fake_proof(&proof);
+                store_aggregation_proof(self.db_pool.clone(), id, hex::encode(proof), fake).await?;
+                AGGREGATION_SUCCESS_COUNTER.inc();
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Proof gen thread: error generating aggregated proof {}, error: {}",
+                    id,
+                    e
+                );
+                mark_aggregation_failed(self.db_pool.clone(), id).await?;
+                AGGREGATION_FAILURE_COUNTER.inc();
+            }
+        }
+        Ok(true)
+    }
+
+    // Aggregates the stored succinct receipts of the blobs with the aggregation guest,
+    // which verifies each of them, and compresses the result into a Groth16 receipt.
+    // Returns the proof encoded in the same ethabi Tuple as the blob proofs.
+    async fn generate_aggregated_proof(&self, aggregation: PendingAggregation) -> Result<Vec<u8>> {
+        let mut stored_receipts: HashMap<String, Option<String>> =
+            retrieve_blob_receipts(self.db_pool.clone(), aggregation.blob_ids.clone())
+                .await?
+                .into_iter()
+                .collect();
+        // In the order of the request, which is the order they are committed in
+        let receipts = aggregation
+            .blob_ids
+            .iter()
+            .map(|blob_id| {
+                let receipt = stored_receipts
+                    .remove(blob_id)
+                    .flatten()
+                    .ok_or_else(|| anyhow::anyhow!("No receipt stored for Blob Id {}", blob_id))?;
+                let receipt = serde_json::from_str::<Receipt>(&receipt)?;
+                if !matches!(
+                    receipt.inner,
+                    InnerReceipt::Succinct(_) | InnerReceipt::Fake(_)
+                ) {
+                    anyhow::bail!("Receipt stored for Blob Id {} is not succinct", blob_id);
+                }
+                Ok(receipt)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let input = AggregationInput {
+            image_id: GUEST_ID,
+            journals: receipts
+                .iter()
+                .map(|receipt| receipt.journal.bytes.clone())
+                .collect(),
+        };
+        let ProveOutput { receipt, .. } =
+            self.prover.aggregate(input.to_stdin()?, receipts).await?;
+
+        let timer = PROOF_COMPRESSION_TIME_HISTOGRAM.start_timer();
+        let receipt = self.prover.compress(receipt).await?;
+        timer.observe_duration();
+
+        encode_proof(&receipt, compute_image_id(AGGREGATOR_ELF)?)
+    }
+
+    // Proves the guest for the pending proof request into the requested kind of receipt (a
+    // succinct one for Groth16), and persists it along with the statistics of its generation.
+    // If the EigenDA cert was provided with the request it is used as is, otherwise it's requested to the disperser.
+    async fn prove_receipt(&self, pending_proof: &PendingProof) -> Result<Receipt> {
+        let input = self.guest_input(pending_proof).await?;
+        let payload_size = input.data.len() as u64;
+        let proving_started = Instant::now();
+        let ProveOutput {
+            receipt,
+            stats: execution,
+        } = run_guest(self.prover.as_ref(), input, pending_proof.receipt_kind).await?;
+        let stats = ProofStats {
+            payload_size,
+            proving_secs: proving_started.elapsed().as_secs_f64(),
+            execution,
+        };
+        if let Some(execution) = &stats.execution {
+            tracing::info!(
+                "Proved {} bytes payload in {:.1}s: {} total cycles, {} user cycles, {} segments",
+                payload_size,
+                stats.proving_secs,
+                execution.total_cycles,
+                execution.user_cycles,
+                execution.segments
+            );
+        }
+
+        store_blob_receipt(
+            self.db_pool.clone(),
+            pending_proof.blob_id.clone(),
+            serde_json::to_string(&receipt)?,
+            &stats,
+            pending_proof.reorgs,
+        )
+        .await?;
+        observe_proof_stats(&stats);
+        Ok(receipt)
+    }
+
+    /// Returns the guest input of the proof request. The input stored by a previous attempt is
+    /// reused as is if its anchor block is recent enough (or fixed by the anchor policy),
+    /// otherwise only its EVM state is fetched again. New inputs are stored so the next
+    /// attempts can reuse them.
+    async fn guest_input(&self, pending_proof: &PendingProof) -> Result<GuestInput> {
+        let settings = InputSettings {
+            chain: self.chain,
+            beacon_committed: self.beacon_api_url.is_some(),
+        };
+        let stored_input = match pending_proof.guest_input.as_deref() {
+            Some(guest_input) => match GuestInput::from_json(guest_input) {
+                Ok(input) => match settings.mismatch(input.settings()) {
+                    Some(InputMismatch::Chain(chain)) => {
+                        tracing::info!(
+                            "Stored guest input of Blob Id {} was built for {}, rebuilding it",
+                            pending_proof.blob_id,
+                            chain
+                        );
+                        None
+                    }
+                    Some(InputMismatch::Commitment) => {
+                        tracing::info!(
+                            "Stored guest input of Blob Id {} has another environment commitment, rebuilding it",
+                            pending_proof.blob_id
+                        );
+                        None
+                    }
+                    None => Some(input),
+                },
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed decoding stored guest input of Blob Id {}: {}",
+                        pending_proof.blob_id,
+                        e
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let input = match stored_input {
+            Some(input) => {
+                let reusable = if !self.is_input_canonical(&input).await {
+                    tracing::info!(
+                        "Guest input of Blob Id {} is anchored at block {} which is not canonical anymore, rebuilding it",
+                        pending_proof.blob_id,
+                        input.anchor_block_number
+                    );
+                    false
+                } else if pending_proof.anchor_policy.is_relative()
+                    && !self.is_input_fresh(&input).await
+                {
+                    tracing::info!(
+                        "Guest input of Blob Id {} anchored at block {} is too old, rebuilding it",
+                        pending_proof.blob_id,
+                        input.anchor_block_number
+                    );
+                    false
+                } else {
+                    true
+                };
+                if reusable {
+                    tracing::info!(
+                        "Reusing guest input of Blob Id {} anchored at block {}",
+                        pending_proof.blob_id,
+                        input.anchor_block_number
+                    );
+                    GUEST_INPUT_REUSED_COUNTER.inc();
+                    return Ok(input);
+                }
+                // The cert and the payload don't change, only the EVM state needs a newer block
+                let anchor_block = self
+                    .resolve_anchor_block(pending_proof.anchor_policy, &input.eigenda_cert)
+                    .await?;
+                build_guest_input(
+                    input.eigenda_cert,
+                    &self.srs,
+                    input.data,
+                    self.rpc_url.clone(),
+                    self.cert_verifier_router_addr,
+                    self.payload_form,
+                    self.chain,
+                    anchor_block,
+                    self.beacon_api_url.clone(),
+                )
+                .await?
+            }
+            None => {
+                let eigenda_cert = match pending_proof.eigenda_cert.as_deref() {
+                    Some(eigenda_cert) => decode_eigenda_cert(eigenda_cert)?,
+                    None => loop {
+                        let blob_key = BlobKey::from_hex(&pending_proof.blob_id)?;
+                        let opt_eigenda_cert = self.cert_retriever.get_cert(&blob_key).await?;
+                        if let Some(opt_eigenda_cert) = opt_eigenda_cert {
+                            break opt_eigenda_cert;
+                        }
+                        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                    },
+                };
+                self.build_input(pending_proof, eigenda_cert).await?
+            }
+        };
+
+        store_guest_input(
+            self.db_pool.clone(),
+            pending_proof.blob_id.clone(),
+            input.to_json()?,
+            input.anchor_block_number,
+            format!("{:#x}", input.anchor_block_hash),
+            input.expiry_block(),
+            pending_proof.reorgs,
+        )
+        .await?;
+        Ok(input)
+    }
+
+    /// Resolves the anchor policy of a proof request into the block its input is anchored to.
+    async fn resolve_anchor_block(
+        &self,
+        anchor_policy: AnchorPolicy,
+        eigenda_cert: &EigenDACert,
+    ) -> Result<u64> {
+        match anchor_policy {
+            AnchorPolicy::Latest => self.eth_rpc.block_number().await,
+            AnchorPolicy::Confirmations(confirmations) => {
+                let latest = self.eth_rpc.block_number().await?;
+                latest.checked_sub(confirmations).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Latest block {} is below the {} required confirmations",
+                        latest,
+                        confirmations
+                    )
+                })
+            }
+            AnchorPolicy::Block(number) => Ok(number),
+            AnchorPolicy::ReferenceBlock => {
+                Ok(eigenda_cert.batch_header.reference_block_number as u64)
+            }
+        }
+    }
+
+    /// Whether the anchor block of the guest input is within `max_input_age_blocks` of the
+    /// latest block, and the proof built from it wouldn't expire within `expiry_margin_blocks`.
+    /// Inputs are considered stale if the latest block can't be retrieved.
+    async fn is_input_fresh(&self, input: &GuestInput) -> bool {
+        if self.max_input_age_blocks == 0 {
+            return false;
+        }
+        match self.eth_rpc.block_number().await {
+            Ok(latest) => {
+                latest.saturating_sub(input.anchor_block_number) <= self.max_input_age_blocks
+                    && latest + self.expiry_margin_blocks < input.expiry_block()
+            }
+            Err(e) => {
+                tracing::warn!("Failed retrieving latest block number: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Whether the block the guest input is anchored to is still the canonical one at its
+    /// height, whatever the anchor policy. Inputs are considered reorged if its hash can't be
+    /// retrieved, rebuilding them needs the RPC anyway.
+    async fn is_input_canonical(&self, input: &GuestInput) -> bool {
+        match self.eth_rpc.block_hash(input.anchor_block_number).await {
+            Ok(hash) => hash == Some(input.anchor_block_hash),
+            Err(e) => {
+                tracing::warn!(
+                    "Failed retrieving hash of block {}: {}",
+                    input.anchor_block_number,
+                    e
+                );
+                false
+            }
+        }
+    }
+
+    /// Notifies the current status of the proof request to the waiters.
+    async fn notify_status(&self, blob_id: String) {
+        self.notifier
+            .notify_stored(self.db_pool.clone(), blob_id)
+            .await;
+    }
+
+    /// Runs the guest for the proof request in the executor, without proving it, and reports
+    /// whether it passes. Unlike the proof generation, it doesn't wait for the disperser to
+    /// have the EigenDA cert.
+    pub async fn dry_run(&self, pending_proof: PendingProof) -> DryRunResult {
+        let blob_id = pending_proof.blob_id.clone();
+        let stdin = match self
+            .current_input(&pending_proof)
+            .await
+            .and_then(|input| input.to_stdin())
+        {
+            Ok(stdin) => stdin,
+            Err(e) => {
+                return dry_run_failed(blob_id, format!("Failed building the guest input: {}", e))
+            }
+        };
+
+        let session = tokio::task::spawn_blocking(move || execute_guest(&stdin)).await;
+        match session {
+            Ok(Ok(session)) => DryRunResult {
+                blob_id,
+                passed: true,
+                error: None,
+                total_cycles: Some(session.total_cycles),
+                user_cycles: Some(session.user_cycles),
+                segments: Some(session.segments.len() as u64),
+                journal: session.journal.map(|journal| hex::encode(journal.bytes)),
+            },
+            Ok(Err(e)) => dry_run_failed(blob_id, e.to_string()),
+            Err(e) => dry_run_failed(blob_id, format!("Guest execution aborted: {}", e)),
+        }
+    }
+
+    /// Exports the guest input of the proof request as a bundle to replay it offline. The input
+    /// stored by the proof generation is exported if there is one, otherwise it is built for
+    /// the latest block, without storing it.
+    pub async fn export_guest_input(
+        &self,
+        proof_request: &PendingProof,
+    ) -> Result<GuestInputBundle> {
+        match proof_request.guest_input.as_deref() {
+            Some(guest_input) => Ok(serde_json::from_str(guest_input)?),
+            None => self.current_input(proof_request).await?.to_bundle(),
+        }
+    }
+
+    // Builds the guest input of the proof request, without waiting for the disperser to have
+    // the EigenDA cert.
+    async fn current_input(&self, pending_proof: &PendingProof) -> Result<GuestInput> {
+        let eigenda_cert = match pending_proof.eigenda_cert.as_deref() {
+            Some(eigenda_cert) => decode_eigenda_cert(eigenda_cert)?,
+            None => {
+                let blob_key = BlobKey::from_hex(&pending_proof.blob_id)?;
+                self.cert_retriever
+                    .get_cert(&blob_key)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("EigenDA cert not available yet"))?
+            }
+        };
+        self.build_input(pending_proof, eigenda_cert).await
+    }
+
+    // Builds the guest input of the proof request for its EigenDA cert.
+    // If the payload was provided with the request it is checked against the cert commitment,
+    // otherwise it's retrieved from the relays.
+    async fn build_input(
+        &self,
+        pending_proof: &PendingProof,
+        eigenda_cert: EigenDACert,
+    ) -> Result<GuestInput> {
+        // Raw bytes dispersed by zksync sequencer to EigenDA
+        let blob_data = match pending_proof.payload.as_deref() {
+            Some(payload) => {
+                let payload = hex::decode(payload.trim_start_matches("0x"))?;
+                verify_payload_commitment(&eigenda_cert, &payload, self.payload_form, &self.srs)?;
+                payload
+            }
+            None => self.retrieve_payload(&eigenda_cert).await?.serialize(),
+        };
+
+        let anchor_block = self
+            .resolve_anchor_block(pending_proof.anchor_policy, &eigenda_cert)
+            .await?;
+        build_guest_input(
+            eigenda_cert,
+            &self.srs,
+            blob_data,
+            self.rpc_url.clone(),
+            self.cert_verifier_router_addr,
+            self.payload_form,
+            self.chain,
+            anchor_block,
+            self.beacon_api_url.clone(),
+        )
+        .await
+    }
+
+    /// Retrieves the payload of the cert from the relays, connecting to them if needed.
+    async fn retrieve_payload(&self, eigenda_cert: &EigenDACert) -> Result<Payload> {
+        let payload = self
+            .connect_relays()
+            .await?
+            .lock()
+            .await
+            .get_payload(eigenda_cert.clone())
+            .await?;
+        Ok(payload)
+    }
+
+    /// Returns the relay payload retriever, connecting to the relays if it wasn't yet.
+    pub async fn connect_relays(&self) -> Result<&Mutex<RelayPayloadRetriever>> {
+        self.retriever
+            .get_or_try_init(|| async {
+                let retriever_config = RelayPayloadRetrieverConfig {
+                    payload_form: self.payload_form,
+                    retrieval_timeout_secs: Duration::from_secs(60),
+                };
+                let srs_config = SRSConfig {
+                    source_path: SRS_PATH.to_string(),
+                    order: SRS_ORDER,
+                    points_to_load: SRS_POINTS_TO_LOAD,
+                };
+                let relay_client = RelayClient::new(self.relay_client_config()).await?;
+                let retriever =
+                    RelayPayloadRetriever::new(retriever_config, srs_config, relay_client)?;
+                anyhow::Ok(Mutex::new(retriever))
+            })
+            .await
+    }
+
+    /// Whether the relay payload retriever was already connected, without connecting it.
+    pub fn relays_connected(&self) -> bool {
+        self.retriever.initialized()
+    }
+
+    /// Configuration of the clients of the relays the payloads are retrieved from.
+    pub fn relay_client_config(&self) -> RelayClientConfig {
+        RelayClientConfig {
+            max_grpc_message_size: SRS_ORDER as usize,
+            relay_clients_keys: self.relay_config.relay_client_keys.clone(),
+            relay_registry_address: self.relay_config.relay_registry_address,
+            eth_rpc_url: SecretUrl::new(self.rpc_url.clone()),
+        }
+    }
+}
+
+fn dry_run_failed(blob_id: String, error: String) -> DryRunResult {
+    DryRunResult {
+        blob_id,
+        passed: false,
+        error: Some(error),
+        total_cycles: None,
+        user_cycles: None,
+        segments: None,
+        journal: None,
+    }
+}
+
+/// Encodes the receipt of the guest with the given image ID as the ethabi Tuple returned by `get_proof`.
+pub fn encode_proof(receipt: &Receipt, image_id: risc0_zkvm::sha::Digest) -> Result<Vec<u8>> {
+    let image_id = image_id.as_bytes().to_vec();
+
+    let block_proof = match &receipt.inner {
+        InnerReceipt::Groth16(inner) => {
+            // The SELECTOR is used to perform an extra check inside the groth16 verifier contract.
+            let mut selector = hex::encode(
+                inner
+                    .verifier_parameters
+                    .as_bytes()
+                    .get(..4)
+                    .ok_or(anyhow::anyhow!("verifier parameters too short"))?,
+            );
+            let seal = hex::encode(inner.clone().seal);
+            selector.push_str(&seal);
+            hex::decode(selector)?
+        }
+        // Dev mode: marked with the fake selector, followed by the claim digest
+        // as expected by the risc0 mock verifier.
+        InnerReceipt::Fake(inner) => [
+            FAKE_PROOF_SELECTOR.as_slice(),
+            inner.claim.digest().as_bytes(),
+        ]
+        .concat(),
+        // Composite and succinct receipts are verified offchain, the seal holds the whole receipt
+        _ => serde_json::to_vec(receipt)?,
+    };
+
+    let proof = ethabi::encode(&[Token::Tuple(vec![
+        Token::Bytes(block_proof),
+        Token::FixedBytes(image_id),
+        Token::Bytes(receipt.journal.bytes.clone()),
+    ])]);
+
+    Ok(proof)
+}
@@ -0,0 +1,81 @@
+//! Tests of the beacon node API client against a local stand-in beacon node.
+
+mod common;
+
+use common::StandIn;
+use host::chain::BeaconApiClient;
+use serde_json::json;
+
+fn head_header(slot: &str) -> String {
+    json!({
+        "execution_optimistic": false,
+        "finalized": false,
+        "data": {
+            "root": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "canonical": true,
+            "header": {
+                "message": {
+                    "slot": slot,
+                    "proposer_index": "1",
+                    "parent_root": "0x0000000000000000000000000000000000000000000000000000000000000002",
+                    "state_root": "0x0000000000000000000000000000000000000000000000000000000000000003",
+                    "body_root": "0x0000000000000000000000000000000000000000000000000000000000000004"
+                },
+                "signature": "0x00"
+            }
+        }
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn head_slot() {
+    let beacon_node = StandIn::start(|request| match request.path.as_str() {
+        "/eth/v1/beacon/headers/head" => (200, head_header("4242")),
+        _ => (
+            404,
+            json!({ "code": 404, "message": "Not found" }).to_string(),
+        ),
+    });
+
+    let beacon_api = BeaconApiClient::new(beacon_node.url.clone());
+    assert_eq!(beacon_api.head_slot().await.unwrap(), 4242);
+    let requests = beacon_node.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/eth/v1/beacon/headers/head");
+}
+
+#[tokio::test]
+async fn head_slot_behind_a_path() {
+    let beacon_node = StandIn::start(|request| match request.path.as_str() {
+        "/beacon/eth/v1/beacon/headers/head" => (200, head_header("7")),
+        _ => (
+            404,
+            json!({ "code": 404, "message": "Not found" }).to_string(),
+        ),
+    });
+
+    let beacon_api = BeaconApiClient::new(beacon_node.url.join("beacon/").unwrap());
+    assert_eq!(beacon_api.head_slot().await.unwrap(), 7);
+}
+
+#[tokio::test]
+async fn head_slot_errors() {
+    let beacon_node = StandIn::start(|request| match request.path.as_str() {
+        "/syncing/eth/v1/beacon/headers/head" => (
+            503,
+            json!({ "code": 503, "message": "Syncing" }).to_string(),
+        ),
+        "/garbled/eth/v1/beacon/headers/head" => (200, json!({ "data": {} }).to_string()),
+        _ => (200, head_header("not a slot")),
+    });
+
+    for path in ["syncing/", "garbled/", "invalid/"] {
+        let beacon_api = BeaconApiClient::new(beacon_node.url.join(path).unwrap());
+        assert!(
+            beacon_api.head_slot().await.is_err(),
+            "{} beacon node should fail",
+            path
+        );
+    }
+}
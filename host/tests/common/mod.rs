@@ -0,0 +1,69 @@
+//! Local HTTP server standing in for the node APIs the service calls, in the tests,
+//! and the database and proving service the tests of the service state run against.
+// Each test crate only uses a part of it
+#![allow(dead_code)]
+
+pub mod db;
+pub mod service;
+
+use std::sync::{Arc, Mutex};
+
+use tiny_http::{Header, Response, Server};
+use url::Url;
+
+/// Request received by a [`StandIn`].
+#[derive(Debug, Clone)]
+pub struct StandInRequest {
+    pub path: String,
+    pub body: String,
+}
+
+/// HTTP server on an ephemeral local port, answering each request with a scripted handler
+/// and recording it. It runs in its own thread until the tests end.
+pub struct StandIn {
+    pub url: Url,
+    requests: Arc<Mutex<Vec<StandInRequest>>>,
+}
+
+impl StandIn {
+    /// Starts the server. The handler returns the HTTP status and the JSON body of the
+    /// response to each request.
+    pub fn start(handler: impl Fn(&StandInRequest) -> (u16, String) + Send + 'static) -> Self {
+        let server = Server::http("127.0.0.1:0").expect("Failed to start the stand-in server");
+        let addr = server
+            .server_addr()
+            .to_ip()
+            .expect("Stand-in server is not listening on an IP address");
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        std::thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let mut body = String::new();
+                let _ = request.as_reader().read_to_string(&mut body);
+                let received = StandInRequest {
+                    path: request.url().to_string(),
+                    body,
+                };
+                let (status, body) = handler(&received);
+                recorded.lock().unwrap().push(received);
+                let response = Response::from_string(body)
+                    .with_status_code(status)
+                    .with_header(
+                        Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap(),
+                    );
+                let _ = request.respond(response);
+            }
+        });
+
+        Self {
+            url: format!("http://{}/", addr).parse().unwrap(),
+            requests,
+        }
+    }
+
+    /// Requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<StandInRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,116 @@
+//! Proving service wired like the host binary, against the test database and a stand-in
+//! RPC node, without the EigenDA disperser and relays: the proof requests give their cert
+//! and payload.
+
+use std::sync::Arc;
+
+use ::common::chain::Chain;
+use ::common::polynomial_form::PolynomialForm;
+use alloy_primitives::Address;
+use async_trait::async_trait;
+use client::types::SrsInfo;
+use host::admin::WorkerControl;
+use host::info::build_service_info;
+use host::notifier::ProofNotifier;
+use host::prover::ProverBackend;
+use host::service::{AnchorPolicy, CertRetriever, ProvingService};
+use host::worker::{ProofWorker, RelayConfig, SRS_ORDER};
+use rust_eigenda_v2_client::core::BlobKey;
+use rust_eigenda_v2_common::{EigenDACert, PayloadForm};
+use rust_kzg_bn254_prover::srs::SRS;
+use sqlx::{Pool, Postgres};
+use tokio::sync::Mutex;
+use url::Url;
+
+/// Chain the test services verify the certs against.
+pub const TEST_CHAIN: Chain = Chain::Devnet(31337);
+
+/// Stands in for the disperser, which never has the certs.
+pub struct NoCerts;
+
+#[async_trait]
+impl CertRetriever for NoCerts {
+    async fn get_cert(&self, _blob_key: &BlobKey) -> anyhow::Result<Option<EigenDACert>> {
+        Ok(None)
+    }
+}
+
+/// Service and the worker proving its requests, sharing their notifier.
+pub struct TestService {
+    pub service: Arc<ProvingService>,
+    pub worker: Arc<ProofWorker>,
+    pub notifier: ProofNotifier,
+}
+
+/// Builds the service on the [`TEST_CHAIN`], see [`test_service_on`].
+pub async fn test_service(
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    rpc_url: Url,
+    prover: Arc<dyn ProverBackend>,
+    dev_mode: bool,
+) -> TestService {
+    test_service_on(TEST_CHAIN, db_pool, rpc_url, prover, dev_mode).await
+}
+
+/// Builds the service for the given chain, proving with the given backend. The worker loop
+/// is not started. No SRS points are loaded, so only the stored guest inputs can be proven.
+pub async fn test_service_on(
+    chain: Chain,
+    db_pool: Arc<Mutex<Pool<Postgres>>>,
+    rpc_url: Url,
+    prover: Arc<dyn ProverBackend>,
+    dev_mode: bool,
+) -> TestService {
+    let notifier = ProofNotifier::new();
+    let control = Arc::new(WorkerControl::load(db_pool.clone()).await.unwrap());
+    let service_info = build_service_info(
+        chain,
+        Address::ZERO,
+        &PolynomialForm::Coeff,
+        AnchorPolicy::Latest,
+        false,
+        0,
+        SrsInfo {
+            path: String::new(),
+            order: SRS_ORDER,
+            points_loaded: 0,
+        },
+        dev_mode,
+    )
+    .unwrap();
+    let worker = Arc::new(ProofWorker::new(
+        db_pool.clone(),
+        Arc::new(NoCerts),
+        RelayConfig {
+            relay_client_keys: vec![],
+            relay_registry_address: Address::ZERO,
+        },
+        SRS {
+            g1: vec![],
+            order: SRS_ORDER,
+        },
+        rpc_url,
+        Address::ZERO,
+        PayloadForm::Coeff,
+        chain,
+        None,
+        notifier.clone(),
+        control,
+        prover,
+        256,
+        32,
+    ));
+    let service = Arc::new(ProvingService::new(
+        db_pool,
+        Arc::new(NoCerts),
+        service_info,
+        notifier.clone(),
+        worker.clone(),
+        32,
+    ));
+    TestService {
+        service,
+        worker,
+        notifier,
+    }
+}
@@ -0,0 +1,152 @@
+//! End to end test of the dev mode proofs: a proof request submitted over JSON RPC is proven
+//! with the dev backend, executing the guest for a real guest input, into a fake proof which
+//! is only served by the services running in dev mode.
+//!
+//! It needs the test database, see `common::db`, and a guest input exported from a service
+//! proving without a beacon API (`cli export-input <blob id> --output <file>`), pointed to by
+//! `TEST_GUEST_INPUT`. The RPC node is stood in, so the input anchor is never rebuilt.
+
+mod common;
+
+use std::{net::SocketAddr, sync::Arc};
+
+use client::proof::{decode_proof, FAKE_PROOF_SELECTOR};
+use client::types::GenerateProofParams;
+use client::{ClientError, ProofState, ProvingServiceClient};
+use common::service::test_service_on;
+use common::{db::test_db, StandIn, StandInRequest};
+use host::db::{retrieve_proof_request, store_guest_input};
+use host::guest_caller::{GuestInput, GuestInputBundle};
+use host::prover::DevProverBackend;
+use host::rpc::build_rpc_handler;
+use host::service::{AnchorPolicy, ProvingService, ReceiptKind};
+use jsonrpc_http_server::ServerBuilder;
+use serde_json::json;
+
+/// Answers `eth_blockNumber` with the block after the anchor, and `eth_getBlockByNumber`
+/// with the anchor hash, so the input is canonical and the proof is fresh.
+fn rpc_node(input: &GuestInput) -> impl Fn(&StandInRequest) -> (u16, String) {
+    let anchor_block = input.anchor_block_number;
+    let anchor_hash = format!("{:#x}", input.anchor_block_hash);
+    move |request| {
+        let request: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        let result = match request["method"].as_str().unwrap() {
+            "eth_blockNumber" => json!(format!("0x{:x}", anchor_block + 1)),
+            "eth_getBlockByNumber" => {
+                assert_eq!(request["params"][0], format!("0x{:x}", anchor_block));
+                json!({ "number": request["params"][0], "hash": anchor_hash })
+            }
+            method => panic!("unexpected RPC call {}", method),
+        };
+        (
+            200,
+            json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string(),
+        )
+    }
+}
+
+/// Serves the JSON RPC API of the service on an ephemeral local port.
+fn start_server(service: Arc<ProvingService>) -> ProvingServiceClient {
+    let server = ServerBuilder::new(build_rpc_handler(service))
+        // The handlers use the database pool of the test runtime
+        .event_loop_executor(tokio::runtime::Handle::current())
+        .start_http(&SocketAddr::from(([127, 0, 0, 1], 0)))
+        .expect("Failed to start the JSON RPC server");
+    let client = ProvingServiceClient::new(format!("http://{}", server.address()));
+    std::thread::spawn(move || server.wait());
+    client
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "needs the test database and an exported guest input"]
+async fn serves_fake_proofs_only_in_dev_mode() {
+    let path = std::env::var("TEST_GUEST_INPUT").expect("TEST_GUEST_INPUT is not set");
+    let json = std::fs::read_to_string(path).unwrap();
+    let bundle: GuestInputBundle = serde_json::from_str(&json).unwrap();
+    let input = GuestInput::from_json(&json).unwrap();
+    assert!(
+        !input.settings().beacon_committed,
+        "The guest input must be exported from a service without beacon API"
+    );
+    let blob_id = input.eigenda_cert.compute_blob_key().unwrap().to_hex();
+
+    let db_pool = test_db().await;
+    // The blob ID is the one of the exported cert, so it's the same on each run
+    {
+        let db_lock = db_pool.lock().await;
+        sqlx::query("DELETE FROM BLOB_PROOFS WHERE BLOB_ID = $1")
+            .bind(&blob_id)
+            .execute(&*db_lock)
+            .await
+            .unwrap();
+    }
+    let node = StandIn::start(rpc_node(&input));
+    let dev = test_service_on(
+        input.chain,
+        db_pool.clone(),
+        node.url.clone(),
+        Arc::new(DevProverBackend),
+        true,
+    )
+    .await;
+    let production = test_service_on(
+        input.chain,
+        db_pool.clone(),
+        node.url.clone(),
+        Arc::new(DevProverBackend),
+        false,
+    )
+    .await;
+    let dev_client = start_server(dev.service);
+    let production_client = start_server(production.service);
+
+    dev_client
+        .generate_proof_with_params(GenerateProofParams {
+            blob_id: blob_id.clone(),
+            eigenda_cert: Some(bundle.eigenda_cert),
+            payload: Some(bundle.data),
+            receipt_kind: Some(ReceiptKind::Groth16),
+            anchor_policy: Some(AnchorPolicy::Block(input.anchor_block_number)),
+        })
+        .await
+        .unwrap();
+    // Stored like a previous attempt did, the worker reuses it instead of building it
+    store_guest_input(
+        db_pool.clone(),
+        blob_id.clone(),
+        input.to_json().unwrap(),
+        input.anchor_block_number,
+        format!("{:#x}", input.anchor_block_hash),
+        input.expiry_block(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    // Proven directly, the worker loop would take the requests left queued by the other tests
+    let pending_proof = retrieve_proof_request(db_pool.clone(), blob_id.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    dev.worker.prove_request(pending_proof).await.unwrap();
+
+    let status = dev_client.get_proof_status(&blob_id).await.unwrap();
+    assert_eq!(status.state, ProofState::Proven);
+    assert!(status.fake);
+    assert!(status.proof.is_some());
+    let proof = dev_client.get_proof(&blob_id).await.unwrap();
+    let decoded = decode_proof(&proof).unwrap();
+    assert!(decoded.fake);
+    assert!(decoded.seal.starts_with(&hex::encode(FAKE_PROOF_SELECTOR)));
+    assert_eq!(decoded.journal.chain_id, input.chain.chain_id());
+
+    // Withheld out of dev mode, keeping the flag telling why
+    let status = production_client.get_proof_status(&blob_id).await.unwrap();
+    assert_eq!(status.state, ProofState::Proven);
+    assert!(status.fake);
+    assert_eq!(status.proof, None);
+    assert!(matches!(
+        production_client.get_proof(&blob_id).await,
+        Err(ClientError::InvalidState(_))
+    ));
+}
@@ -0,0 +1,300 @@
+//! Tests of the proof freshness, and of the selection of the proofs the freshness monitor
+//! proves again. The selection tests need the test database, see `common::db`.
+
+mod common;
+
+use std::sync::Arc;
+
+use alloy_primitives::B256;
+use common::db::{test_db, unique_blob_id};
+use host::db::{
+    mark_blob_proof_consumed, reprove_blob_proof, requeue_reorged_blob_proof,
+    retrieve_blob_id_proof, retrieve_expiring_blob_proofs, store_blob_proof,
+    store_blob_proof_request, store_guest_input,
+};
+use host::service::{AnchorPolicy, ProofFreshness, ReceiptKind};
+use sqlx::{Pool, Postgres};
+use tokio::sync::Mutex;
+
+type DbPool = Arc<Mutex<Pool<Postgres>>>;
+
+#[test]
+fn expiring_within_the_margin() {
+    let margin = 10;
+    for (latest_block, freshness) in [
+        (0, ProofFreshness::Fresh),
+        (989, ProofFreshness::Fresh),
+        (990, ProofFreshness::Expiring),
+        (999, ProofFreshness::Expiring),
+        // Still verifiable at the expiry block itself
+        (1000, ProofFreshness::Expiring),
+        (1001, ProofFreshness::Expired),
+    ] {
+        assert_eq!(
+            ProofFreshness::at(1000, latest_block, margin),
+            freshness,
+            "latest block {}",
+            latest_block
+        );
+    }
+}
+
+#[test]
+fn expiring_without_a_margin() {
+    assert_eq!(ProofFreshness::at(1000, 999, 0), ProofFreshness::Fresh);
+    assert_eq!(ProofFreshness::at(1000, 1000, 0), ProofFreshness::Expiring);
+    assert_eq!(ProofFreshness::at(1000, 1001, 0), ProofFreshness::Expired);
+}
+
+#[test]
+fn expiring_with_a_margin_past_the_last_block() {
+    assert_eq!(
+        ProofFreshness::at(u64::MAX, u64::MAX - 1, u64::MAX),
+        ProofFreshness::Expiring
+    );
+    assert_eq!(
+        ProofFreshness::at(u64::MAX, 0, u64::MAX - 1),
+        ProofFreshness::Fresh
+    );
+}
+
+fn anchor_hash() -> String {
+    format!("{:#x}", B256::repeat_byte(0x10))
+}
+
+/// Stores a proof request proven from an input anchored so that its proof expires at
+/// `expiry_block`, and returns its blob ID.
+async fn proven(
+    db_pool: &DbPool,
+    name: &str,
+    receipt_kind: ReceiptKind,
+    anchor_policy: AnchorPolicy,
+    expiry_block: u64,
+    fake: bool,
+) -> String {
+    let blob_id = unique_blob_id(name);
+    store_blob_proof_request(
+        db_pool.clone(),
+        blob_id.clone(),
+        None,
+        None,
+        receipt_kind,
+        anchor_policy,
+    )
+    .await
+    .unwrap();
+    store_guest_input(
+        db_pool.clone(),
+        blob_id.clone(),
+        "{}".to_string(),
+        expiry_block - 256,
+        anchor_hash(),
+        expiry_block,
+        0,
+    )
+    .await
+    .unwrap();
+    assert!(
+        store_blob_proof(db_pool.clone(), blob_id.clone(), "00".to_string(), fake, 0)
+            .await
+            .unwrap()
+    );
+    blob_id
+}
+
+async fn groth16(db_pool: &DbPool, name: &str, expiry_block: u64) -> String {
+    proven(
+        db_pool,
+        name,
+        ReceiptKind::Groth16,
+        AnchorPolicy::Latest,
+        expiry_block,
+        false,
+    )
+    .await
+}
+
+/// Blob IDs of the proofs the freshness monitor would prove again, among the given ones.
+async fn expiring(
+    db_pool: &DbPool,
+    latest_block: u64,
+    margin: u64,
+    blob_ids: &[&str],
+) -> Vec<String> {
+    retrieve_expiring_blob_proofs(db_pool.clone(), latest_block + margin, i64::MAX)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|record| record.blob_id)
+        .filter(|blob_id| blob_ids.contains(&blob_id.as_str()))
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "needs the test database"]
+async fn selects_the_proofs_expiring_within_the_margin() {
+    let db_pool = test_db().await;
+    let expired = groth16(&db_pool, "expired", 900).await;
+    let at_margin = groth16(&db_pool, "at-margin", 1010).await;
+    let past_margin = groth16(&db_pool, "past-margin", 1011).await;
+    let confirmations = proven(
+        &db_pool,
+        "confirmations",
+        ReceiptKind::Groth16,
+        AnchorPolicy::Confirmations(12),
+        1000,
+        false,
+    )
+    .await;
+
+    let blob_ids = [
+        expired.as_str(),
+        at_margin.as_str(),
+        past_margin.as_str(),
+        confirmations.as_str(),
+    ];
+    assert_eq!(
+        expiring(&db_pool, 1000, 10, &blob_ids).await,
+        vec![expired.clone(), at_margin.clone(), confirmations.clone()]
+    );
+    assert_eq!(
+        expiring(&db_pool, 1001, 10, &blob_ids).await,
+        vec![expired, at_margin, past_margin, confirmations]
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs the test database"]
+async fn skips_the_proofs_not_worth_proving_again() {
+    let db_pool = test_db().await;
+    let consumed = groth16(&db_pool, "consumed", 1000).await;
+    assert!(mark_blob_proof_consumed(db_pool.clone(), consumed.clone())
+        .await
+        .unwrap());
+    let succinct = proven(
+        &db_pool,
+        "succinct",
+        ReceiptKind::Succinct,
+        AnchorPolicy::Latest,
+        1000,
+        false,
+    )
+    .await;
+    let composite = proven(
+        &db_pool,
+        "composite",
+        ReceiptKind::Composite,
+        AnchorPolicy::Latest,
+        1000,
+        false,
+    )
+    .await;
+    let fake = proven(
+        &db_pool,
+        "fake",
+        ReceiptKind::Groth16,
+        AnchorPolicy::Latest,
+        1000,
+        true,
+    )
+    .await;
+    // Anchored to a fixed block, proving them again gives the same proof
+    let fixed_block = proven(
+        &db_pool,
+        "fixed-block",
+        ReceiptKind::Groth16,
+        AnchorPolicy::Block(744),
+        1000,
+        false,
+    )
+    .await;
+    let reference_block = proven(
+        &db_pool,
+        "reference-block",
+        ReceiptKind::Groth16,
+        AnchorPolicy::ReferenceBlock,
+        1000,
+        false,
+    )
+    .await;
+    let queued = unique_blob_id("queued");
+    store_blob_proof_request(
+        db_pool.clone(),
+        queued.clone(),
+        None,
+        None,
+        ReceiptKind::Groth16,
+        AnchorPolicy::Latest,
+    )
+    .await
+    .unwrap();
+
+    let blob_ids = [
+        consumed.as_str(),
+        succinct.as_str(),
+        composite.as_str(),
+        fake.as_str(),
+        fixed_block.as_str(),
+        reference_block.as_str(),
+        queued.as_str(),
+    ];
+    assert!(expiring(&db_pool, 1000, 10, &blob_ids).await.is_empty());
+    // Consumed proofs are not queued again even if they are selected in the meantime
+    assert!(!reprove_blob_proof(db_pool.clone(), consumed).await.unwrap());
+}
+
+#[tokio::test]
+#[ignore = "needs the test database"]
+async fn reproving_keeps_the_reorgs_guard() {
+    let db_pool = test_db().await;
+    let blob_id = groth16(&db_pool, "reorged", 1000).await;
+    // Proven again after a reorg of its anchor block
+    assert!(
+        requeue_reorged_blob_proof(db_pool.clone(), blob_id.clone(), anchor_hash())
+            .await
+            .unwrap()
+    );
+    store_guest_input(
+        db_pool.clone(),
+        blob_id.clone(),
+        "{}".to_string(),
+        744,
+        anchor_hash(),
+        1000,
+        1,
+    )
+    .await
+    .unwrap();
+    assert!(
+        store_blob_proof(db_pool.clone(), blob_id.clone(), "00".to_string(), false, 1)
+            .await
+            .unwrap()
+    );
+
+    assert!(reprove_blob_proof(db_pool.clone(), blob_id.clone())
+        .await
+        .unwrap());
+    let record = retrieve_blob_id_proof(db_pool.clone(), blob_id.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(record.proof, None);
+    assert_eq!(record.reorgs, 1);
+
+    // A proof anchored before the reorg is still refused, the new one is stored
+    assert!(
+        !store_blob_proof(db_pool.clone(), blob_id.clone(), "01".to_string(), false, 0)
+            .await
+            .unwrap()
+    );
+    assert!(
+        store_blob_proof(db_pool.clone(), blob_id.clone(), "02".to_string(), false, 1)
+            .await
+            .unwrap()
+    );
+    let record = retrieve_blob_id_proof(db_pool, blob_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(record.proof.as_deref(), Some("02"));
+}
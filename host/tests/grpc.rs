@@ -0,0 +1,162 @@
+//! Tests of the gRPC API: the status codes of the service errors, and the proof updates
+//! streamed by an in-process server. The server tests need the test database, see `common::db`.
+
+mod common;
+
+use std::{net::SocketAddr, sync::Arc};
+
+use common::db::{test_db, unique_blob_id};
+use common::service::test_service;
+use host::db::{store_blob_proof, store_blob_proof_request};
+use host::grpc::proto::{
+    proving_service_client::ProvingServiceClient, proving_service_server::ProvingServiceServer,
+    BlobIdRequest, ProofState, SubmitProofRequest,
+};
+use host::grpc::GrpcProvingService;
+use host::notifier::ProofNotifier;
+use host::prover::DevProverBackend;
+use host::service::{AnchorPolicy, ReceiptKind, ServiceError};
+use sqlx::{Pool, Postgres};
+use tokio::{net::TcpListener, sync::Mutex};
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::{transport::Channel, Code, Status};
+
+#[test]
+fn maps_service_errors_onto_status_codes() {
+    for (err, code) in [
+        (
+            ServiceError::InvalidParams("Invalid Blob ID".to_string()),
+            Code::InvalidArgument,
+        ),
+        (ServiceError::AlreadySubmitted, Code::AlreadyExists),
+        (ServiceError::NotFound, Code::NotFound),
+        (ServiceError::AggregationNotFound, Code::NotFound),
+        (ServiceError::Queued, Code::Unavailable),
+        (ServiceError::Failed, Code::FailedPrecondition),
+        (
+            ServiceError::InvalidState("Proof request is cancelled".to_string()),
+            Code::FailedPrecondition,
+        ),
+        (ServiceError::Internal, Code::Internal),
+    ] {
+        let message = err.to_string();
+        let status = Status::from(err);
+        assert_eq!(status.code(), code, "{}", message);
+        assert_eq!(status.message(), message);
+    }
+}
+
+/// Serves the gRPC API of a test service on an ephemeral local port.
+async fn start_server() -> (
+    ProvingServiceClient<Channel>,
+    Arc<Mutex<Pool<Postgres>>>,
+    ProofNotifier,
+) {
+    let db_pool = test_db().await;
+    // The node is only asked for the latest block, to report the freshness of the proofs
+    let rpc_url = "http://127.0.0.1:1/".parse().unwrap();
+    let test = test_service(db_pool.clone(), rpc_url, Arc::new(DevProverBackend), false).await;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr: SocketAddr = listener.local_addr().unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(ProvingServiceServer::new(GrpcProvingService::new(
+                test.service,
+            )))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    let client = ProvingServiceClient::connect(format!("http://{}", addr))
+        .await
+        .unwrap();
+    (client, db_pool, test.notifier)
+}
+
+#[tokio::test]
+#[ignore = "needs the test database"]
+async fn streams_updates_until_a_terminal_state() {
+    let (mut client, db_pool, notifier) = start_server().await;
+    let blob_id = unique_blob_id("streamed");
+    store_blob_proof_request(
+        db_pool.clone(),
+        blob_id.clone(),
+        None,
+        None,
+        ReceiptKind::Groth16,
+        AnchorPolicy::Latest,
+    )
+    .await
+    .unwrap();
+
+    let mut updates = client
+        .stream_proof_updates(BlobIdRequest {
+            blob_id: blob_id.clone(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    let queued = updates.message().await.unwrap().unwrap();
+    assert_eq!(queued.blob_id, blob_id);
+    assert_eq!(queued.state(), ProofState::Queued);
+    assert!(queued.proof.is_empty());
+
+    // Proven like the worker does it
+    assert!(store_blob_proof(
+        db_pool.clone(),
+        blob_id.clone(),
+        "c0ffee".to_string(),
+        false,
+        0
+    )
+    .await
+    .unwrap());
+    notifier.notify_stored(db_pool, blob_id.clone()).await;
+
+    let proven = updates.message().await.unwrap().unwrap();
+    assert_eq!(proven.state(), ProofState::Proven);
+    assert_eq!(proven.proof, [0xc0, 0xff, 0xee]);
+    // The stream ends with the terminal state
+    assert!(updates.message().await.unwrap().is_none());
+}
+
+#[tokio::test]
+#[ignore = "needs the test database"]
+async fn returns_the_status_codes_of_the_service_errors() {
+    let (mut client, db_pool, _) = start_server().await;
+
+    let unknown = || BlobIdRequest {
+        blob_id: unique_blob_id("unknown"),
+    };
+    let err = client.get_proof_status(unknown()).await.unwrap_err();
+    assert_eq!(err.code(), Code::NotFound);
+    let err = client.stream_proof_updates(unknown()).await.unwrap_err();
+    assert_eq!(err.code(), Code::NotFound);
+
+    let err = client
+        .submit_proof(SubmitProofRequest {
+            blob_id: unique_blob_id("submitted"),
+            anchor_policy: "earliest".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+    assert!(err.message().contains("earliest"), "{}", err.message());
+
+    let queued = unique_blob_id("queued");
+    store_blob_proof_request(
+        db_pool,
+        queued.clone(),
+        None,
+        None,
+        ReceiptKind::Groth16,
+        AnchorPolicy::Latest,
+    )
+    .await
+    .unwrap();
+    let err = client
+        .get_proof(BlobIdRequest { blob_id: queued })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::Unavailable);
+}
@@ -0,0 +1,55 @@
+//! Tests of the check deciding if a stored guest input can be reused by the service.
+
+use common::chain::Chain;
+use host::guest_caller::{InputMismatch, InputSettings};
+
+fn settings(chain: Chain, beacon_committed: bool) -> InputSettings {
+    InputSettings {
+        chain,
+        beacon_committed,
+    }
+}
+
+#[test]
+fn reuses_inputs_built_with_the_same_settings() {
+    for beacon_committed in [false, true] {
+        let current = settings(Chain::Sepolia, beacon_committed);
+        assert_eq!(current.mismatch(current), None);
+    }
+    let devnet = settings(Chain::Devnet(1337), false);
+    assert_eq!(devnet.mismatch(settings(Chain::Devnet(1337), false)), None);
+}
+
+#[test]
+fn rebuilds_inputs_of_other_chains() {
+    let current = settings(Chain::Sepolia, false);
+    assert_eq!(
+        current.mismatch(settings(Chain::Holesky, false)),
+        Some(InputMismatch::Chain(Chain::Holesky))
+    );
+    // Devnets are told apart by their chain ID
+    let devnet = settings(Chain::Devnet(1337), false);
+    assert_eq!(
+        devnet.mismatch(settings(Chain::Devnet(31337), false)),
+        Some(InputMismatch::Chain(Chain::Devnet(31337)))
+    );
+    // The chain is reported first when both differ
+    assert_eq!(
+        current.mismatch(settings(Chain::Mainnet, true)),
+        Some(InputMismatch::Chain(Chain::Mainnet))
+    );
+}
+
+#[test]
+fn rebuilds_inputs_with_the_other_commitment() {
+    let block_committed = settings(Chain::Sepolia, false);
+    let beacon_committed = settings(Chain::Sepolia, true);
+    assert_eq!(
+        block_committed.mismatch(beacon_committed),
+        Some(InputMismatch::Commitment)
+    );
+    assert_eq!(
+        beacon_committed.mismatch(block_committed),
+        Some(InputMismatch::Commitment)
+    );
+}
@@ -0,0 +1,44 @@
+//! Tests that the journal committed by the guest is decoded back by the service and its clients.
+
+use client::proof::decode_journal;
+use common::output::Output;
+
+fn output(chain_id: u64) -> Output {
+    Output {
+        hash: vec![0x11; 32],
+        env_commitment: vec![0x22; 64],
+        proof: vec![0x33; 32],
+        chain_id,
+    }
+}
+
+#[test]
+fn decodes_the_encoded_output() {
+    for chain_id in [1, 17000, u64::MAX] {
+        let journal = output(chain_id).abi_encode();
+
+        let decoded = Output::abi_decode(&journal).unwrap();
+        let expected = output(chain_id);
+        assert_eq!(decoded.hash, expected.hash);
+        assert_eq!(decoded.env_commitment, expected.env_commitment);
+        assert_eq!(decoded.proof, expected.proof);
+        assert_eq!(decoded.chain_id, chain_id);
+
+        let decoded = decode_journal(&journal).unwrap();
+        assert_eq!(decoded.hash, hex::encode(&expected.hash));
+        assert_eq!(
+            decoded.env_commitment,
+            hex::encode(&expected.env_commitment)
+        );
+        assert_eq!(decoded.kzg_proof, hex::encode(&expected.proof));
+        assert_eq!(decoded.chain_id, chain_id);
+    }
+}
+
+#[test]
+fn rejects_truncated_journals() {
+    let journal = output(1).abi_encode();
+    let truncated = &journal[..journal.len() - 32];
+    assert!(Output::abi_decode(truncated).is_err());
+    assert!(decode_journal(truncated).is_err());
+}
@@ -0,0 +1,187 @@
+//! Tests of the remote prover backend against a local stand-in remote prover,
+//! and of the receipts of the mock prover backend.
+
+mod common;
+
+use ::common::output::{AggregatedBlob, AggregatedOutput, Output};
+use client::types::{ExecutionStats, ReceiptKind};
+use common::{StandIn, StandInRequest};
+use host::guest_caller::AggregationInput;
+use host::prover::{
+    AggregateRequest, MockProverBackend, ProveOutput, ProverBackend, RemoteProverBackend,
+};
+use risc0_zkvm::{sha::Digest, FakeReceipt, InnerReceipt, Receipt, ReceiptClaim};
+
+fn fake_receipt(journal: &[u8]) -> Receipt {
+    let claim = ReceiptClaim::ok(Digest::from([7u32; 8]), journal.to_vec());
+    Receipt::new(
+        InnerReceipt::Fake(FakeReceipt::new(claim)),
+        journal.to_vec(),
+    )
+}
+
+fn prove_output(journal: &[u8]) -> String {
+    serde_json::to_string(&ProveOutput {
+        receipt: fake_receipt(journal),
+        stats: Some(ExecutionStats {
+            total_cycles: 1 << 20,
+            user_cycles: 1 << 19,
+            paging_cycles: 1 << 10,
+            segments: 1,
+        }),
+    })
+    .unwrap()
+}
+
+/// Answers like the `remote-prover` binary, checking what it receives.
+fn remote_prover(request: &StandInRequest) -> (u16, String) {
+    match request.path.as_str() {
+        "/prove?receipt_kind=succinct" => {
+            assert_eq!(request.body, "guest stdin");
+            (200, prove_output(b"blob journal"))
+        }
+        "/aggregate" => {
+            let aggregate: AggregateRequest = serde_json::from_str(&request.body).unwrap();
+            assert_eq!(aggregate.stdin, b"aggregation stdin");
+            assert_eq!(aggregate.receipts.len(), 2);
+            (200, prove_output(b"aggregation journal"))
+        }
+        // Returns the receipt it was given
+        "/compress" => (200, request.body.clone()),
+        _ => (404, "{}".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn proves_on_the_remote_prover() {
+    let remote = StandIn::start(remote_prover);
+    let backend = RemoteProverBackend::new(remote.url.clone(), true);
+
+    let output = backend
+        .prove(b"guest stdin".to_vec(), ReceiptKind::Succinct)
+        .await
+        .unwrap();
+    assert_eq!(output.receipt.journal.bytes, b"blob journal");
+    assert_eq!(output.stats.unwrap().total_cycles, 1 << 20);
+
+    let receipts = vec![fake_receipt(b"first"), fake_receipt(b"second")];
+    let output = backend
+        .aggregate(b"aggregation stdin".to_vec(), receipts)
+        .await
+        .unwrap();
+    assert_eq!(output.receipt.journal.bytes, b"aggregation journal");
+
+    let receipt = backend.compress(fake_receipt(b"succinct")).await.unwrap();
+    assert_eq!(receipt.journal.bytes, b"succinct");
+
+    let paths: Vec<String> = remote
+        .requests()
+        .into_iter()
+        .map(|request| request.path)
+        .collect();
+    assert_eq!(
+        paths,
+        ["/prove?receipt_kind=succinct", "/aggregate", "/compress"]
+    );
+}
+
+#[tokio::test]
+async fn fails_when_the_remote_prover_fails() {
+    let remote = StandIn::start(|_| (503, "prover busy".to_string()));
+    let backend = RemoteProverBackend::new(remote.url.clone(), true);
+
+    let err = backend
+        .prove(b"guest stdin".to_vec(), ReceiptKind::Groth16)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("503") && err.contains("prover busy"),
+        "{}",
+        err
+    );
+    assert!(backend
+        .aggregate(b"aggregation stdin".to_vec(), vec![])
+        .await
+        .is_err());
+    assert!(backend.compress(fake_receipt(b"succinct")).await.is_err());
+}
+
+#[tokio::test]
+async fn refuses_fake_receipts_outside_dev_mode() {
+    let remote = StandIn::start(remote_prover);
+    let backend = RemoteProverBackend::new(remote.url.clone(), false);
+
+    let err = backend
+        .prove(b"guest stdin".to_vec(), ReceiptKind::Succinct)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("fake receipt"), "{}", err);
+    let receipts = vec![fake_receipt(b"first"), fake_receipt(b"second")];
+    assert!(backend
+        .aggregate(b"aggregation stdin".to_vec(), receipts)
+        .await
+        .is_err());
+    assert!(backend.compress(fake_receipt(b"succinct")).await.is_err());
+}
+
+fn blob_output(byte: u8) -> Output {
+    Output {
+        hash: vec![byte; 32],
+        env_commitment: vec![byte; 64],
+        proof: vec![byte; 32],
+        chain_id: 17000,
+    }
+}
+
+#[tokio::test]
+async fn mock_aggregates_the_blob_outputs() {
+    let backend = MockProverBackend::new().unwrap();
+    let input = AggregationInput {
+        image_id: [7; 8],
+        journals: vec![blob_output(1).abi_encode(), blob_output(2).abi_encode()],
+    };
+
+    let output = backend
+        .aggregate(input.to_stdin().unwrap(), vec![])
+        .await
+        .unwrap();
+    let expected = AggregatedOutput {
+        image_id: Digest::from([7u32; 8]).as_bytes().to_vec(),
+        blobs: [1, 2]
+            .into_iter()
+            .map(|byte| {
+                let blob = blob_output(byte);
+                AggregatedBlob {
+                    hash: blob.hash,
+                    env_commitment: blob.env_commitment,
+                    chain_id: blob.chain_id,
+                }
+            })
+            .collect(),
+    };
+    assert_eq!(output.receipt.journal.bytes, expected.abi_encode());
+    assert!(matches!(output.receipt.inner, InnerReceipt::Fake(_)));
+}
+
+#[tokio::test]
+async fn mock_refuses_what_the_guests_would() {
+    let backend = MockProverBackend::new().unwrap();
+    // Not a guest stdin
+    assert!(backend
+        .prove(vec![0xff; 64], ReceiptKind::Groth16)
+        .await
+        .is_err());
+
+    for journals in [vec![], vec![b"not a blob output".to_vec()]] {
+        let input = AggregationInput {
+            image_id: [7; 8],
+            journals,
+        };
+        assert!(backend
+            .aggregate(input.to_stdin().unwrap(), vec![])
+            .await
+            .is_err());
+    }
+}
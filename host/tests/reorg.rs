@@ -0,0 +1,104 @@
+//! Tests of the reorg detection against a local stand-in RPC node serving a scripted chain.
+
+mod common;
+
+use std::collections::HashMap;
+
+use alloy_primitives::B256;
+use common::{StandIn, StandInRequest};
+use host::chain::EthRpcClient;
+use host::db::BlobProofRecord;
+use host::reorg::{find_reorged_anchors, ReorgedAnchor};
+use host::service::{AnchorPolicy, ReceiptKind};
+use serde_json::json;
+
+/// Answers `eth_getBlockByNumber` with the scripted hash of each block, and `null` past them.
+fn rpc_node(chain: HashMap<u64, B256>) -> impl Fn(&StandInRequest) -> (u16, String) {
+    move |request| {
+        let request: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(request["method"], "eth_getBlockByNumber");
+        let number = request["params"][0]
+            .as_str()
+            .unwrap()
+            .trim_start_matches("0x");
+        let block = chain
+            .get(&u64::from_str_radix(number, 16).unwrap())
+            .map(|hash| json!({ "number": request["params"][0], "hash": format!("{:#x}", hash) }));
+        (
+            200,
+            json!({ "jsonrpc": "2.0", "id": request["id"], "result": block }).to_string(),
+        )
+    }
+}
+
+fn hash(byte: u8) -> B256 {
+    B256::repeat_byte(byte)
+}
+
+/// Proven record if `proven`, otherwise a queued one with a stored guest input.
+fn record(blob_id: &str, proven: bool, anchor: Option<(u64, B256)>) -> BlobProofRecord {
+    BlobProofRecord {
+        blob_id: blob_id.to_string(),
+        proof: proven.then(|| "00".to_string()),
+        failed: false,
+        cancelled: false,
+        receipt_kind: ReceiptKind::default(),
+        fake: false,
+        anchor_policy: AnchorPolicy::default(),
+        anchor_block: anchor.map(|(block, _)| block),
+        expiry_block: anchor.map(|(block, _)| block + 256),
+        consumed: false,
+        anchor_hash: anchor.map(|(_, hash)| format!("{:#x}", hash)),
+        reorgs: 0,
+        stats: None,
+    }
+}
+
+#[tokio::test]
+async fn finds_reorged_proofs_and_inputs() {
+    let node = StandIn::start(rpc_node(HashMap::from([
+        (100, hash(0x10)),
+        (101, hash(0x11)),
+        (102, hash(0x12)),
+    ])));
+    let eth_rpc = EthRpcClient::new(node.url.clone());
+
+    let records = vec![
+        record("canonical-proof", true, Some((100, hash(0x10)))),
+        record("reorged-proof", true, Some((101, hash(0xaa)))),
+        record("canonical-input", false, Some((101, hash(0x11)))),
+        record("reorged-input", false, Some((102, hash(0xbb)))),
+        // Not known by the node yet
+        record("ahead", true, Some((103, hash(0x13)))),
+        record("not-anchored", false, None),
+    ];
+    let reorged = find_reorged_anchors(&eth_rpc, records).await.unwrap();
+    assert_eq!(
+        reorged,
+        vec![
+            ReorgedAnchor {
+                blob_id: "reorged-proof".to_string(),
+                anchor_block: 101,
+                anchor_hash: format!("{:#x}", hash(0xaa)),
+                canonical_hash: hash(0x11),
+            },
+            ReorgedAnchor {
+                blob_id: "reorged-input".to_string(),
+                anchor_block: 102,
+                anchor_hash: format!("{:#x}", hash(0xbb)),
+                canonical_hash: hash(0x12),
+            },
+        ]
+    );
+    // Each anchor block is only requested once
+    assert_eq!(node.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn fails_when_the_node_fails() {
+    let node = StandIn::start(|_| (503, "{}".to_string()));
+    let eth_rpc = EthRpcClient::new(node.url.clone());
+
+    let records = vec![record("proof", true, Some((100, hash(0x10))))];
+    assert!(find_reorged_anchors(&eth_rpc, records).await.is_err());
+}
@@ -0,0 +1,88 @@
+//! Tests of the REST API routing, and of its request body limit.
+
+use std::io::Read;
+
+use host::rest::{read_body, route, Route, MAX_BODY_BYTES};
+use host::service::ProofState;
+use tiny_http::Method;
+
+#[test]
+fn routes_encoded_blob_ids() {
+    assert_eq!(
+        route(&Method::Get, "/proofs/01ab%63d").unwrap(),
+        Route::GetProof("01abcd".to_string())
+    );
+    assert_eq!(
+        route(&Method::Get, "/proofs/01abcd/").unwrap(),
+        Route::GetProof("01abcd".to_string())
+    );
+    // Encoded slashes belong to the blob ID, unlike the path separators
+    assert_eq!(
+        route(&Method::Get, "/proofs/01%2Fab").unwrap(),
+        Route::GetProof("01/ab".to_string())
+    );
+    assert_eq!(
+        route(&Method::Get, "/proofs/01/ab")
+            .unwrap_err()
+            .status_code,
+        404
+    );
+    assert_eq!(
+        route(&Method::Get, "/proofs/%FF%FE")
+            .unwrap_err()
+            .status_code,
+        400
+    );
+}
+
+#[test]
+fn routes_state_filters() {
+    assert_eq!(
+        route(&Method::Get, "/proofs").unwrap(),
+        Route::ListProofs(None)
+    );
+    assert_eq!(
+        route(&Method::Get, "/proofs?state=proven").unwrap(),
+        Route::ListProofs(Some(ProofState::Proven))
+    );
+    assert_eq!(
+        route(&Method::Get, "/proofs/?limit=10&state=cancel%6Ced").unwrap(),
+        Route::ListProofs(Some(ProofState::Cancelled))
+    );
+
+    let err = route(&Method::Get, "/proofs?state=expired").unwrap_err();
+    assert_eq!(err.status_code, 400);
+    assert!(err.message.contains("expired"), "{}", err.message);
+}
+
+#[test]
+fn routes_the_other_requests() {
+    assert_eq!(
+        route(&Method::Post, "/proofs").unwrap(),
+        Route::GenerateProof
+    );
+    assert_eq!(route(&Method::Get, "/info").unwrap(), Route::ServiceInfo);
+    assert_eq!(
+        route(&Method::Get, "/openapi.json").unwrap(),
+        Route::OpenApi
+    );
+    for (method, url) in [
+        (Method::Delete, "/proofs/01abcd"),
+        (Method::Post, "/proofs/01abcd"),
+        (Method::Get, "/metrics"),
+    ] {
+        assert_eq!(route(&method, url).unwrap_err().status_code, 404, "{}", url);
+    }
+}
+
+#[test]
+fn limits_the_body_size() {
+    let body = read_body(std::io::repeat(b'a').take(MAX_BODY_BYTES)).unwrap();
+    assert_eq!(body.len() as u64, MAX_BODY_BYTES);
+
+    let err = read_body(std::io::repeat(b'a').take(MAX_BODY_BYTES + 1)).unwrap_err();
+    assert_eq!(err.status_code, 413);
+
+    let err = read_body(&[0xff, 0xfe][..]).unwrap_err();
+    assert_eq!(err.status_code, 400);
+}
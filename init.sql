@@ -2,5 +2,58 @@ CREATE TABLE IF NOT EXISTS blob_proofs (
     id SERIAL PRIMARY KEY,
     blob_id TEXT UNIQUE,
     proof TEXT,
-    failed BOOLEAN DEFAULT FALSE
+    failed BOOLEAN DEFAULT FALSE,
+    cancelled BOOLEAN DEFAULT FALSE,
+    eigenda_cert TEXT,
+    payload TEXT,
+    receipt TEXT,
+    receipt_kind TEXT DEFAULT 'groth16',
+    fake BOOLEAN DEFAULT FALSE,
+    payload_size BIGINT,
+    proving_secs DOUBLE PRECISION,
+    total_cycles BIGINT,
+    user_cycles BIGINT,
+    paging_cycles BIGINT,
+    segments BIGINT,
+    anchor_policy TEXT DEFAULT 'latest',
+    guest_input TEXT,
+    anchor_block BIGINT,
+    expiry_block BIGINT,
+    consumed BOOLEAN DEFAULT FALSE,
+    anchor_hash TEXT,
+    reorgs INTEGER DEFAULT 0
+);
+
+-- Databases created before a column was added get it here, with its default for the existing rows
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS cancelled BOOLEAN DEFAULT FALSE;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS eigenda_cert TEXT;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS payload TEXT;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS receipt TEXT;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS receipt_kind TEXT DEFAULT 'groth16';
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS fake BOOLEAN DEFAULT FALSE;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS payload_size BIGINT;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS proving_secs DOUBLE PRECISION;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS total_cycles BIGINT;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS user_cycles BIGINT;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS paging_cycles BIGINT;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS segments BIGINT;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS anchor_policy TEXT DEFAULT 'latest';
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS guest_input TEXT;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS anchor_block BIGINT;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS expiry_block BIGINT;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS consumed BOOLEAN DEFAULT FALSE;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS anchor_hash TEXT;
+ALTER TABLE blob_proofs ADD COLUMN IF NOT EXISTS reorgs INTEGER DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS aggregations (
+    id SERIAL PRIMARY KEY,
+    blob_ids TEXT[] NOT NULL,
+    proof TEXT,
+    failed BOOLEAN DEFAULT FALSE,
+    fake BOOLEAN DEFAULT FALSE
+);
+
+CREATE TABLE IF NOT EXISTS worker_state (
+    id INTEGER PRIMARY KEY,
+    paused BOOLEAN DEFAULT FALSE
 );
@@ -0,0 +1,56 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![no_main]
+
+use common::output::{AggregatedBlob, AggregatedOutput, Output};
+use risc0_zkvm::{guest::env, sha::Digest};
+
+risc0_zkvm::guest::entry!(main);
+
+/// This guest aggregates the proofs of many blobs into one, so they can be verified
+/// onchain with a single Groth16 verification.
+/// It receives the image ID of the blob guest and the journals of its receipts, and verifies
+/// each of them with `env::verify`: the receipts are added as assumptions by the host, and
+/// are resolved when the aggregation is proven, so the aggregated proof only holds if all of them do.
+/// It commits the blob guest image ID, along with the EigenDA hash, steel environment
+/// commitment and chain ID of every verified blob, in order.
+fn main() {
+    // Image ID of the guest that proved each blob
+    let image_id: [u32; 8] = env::read();
+    // Journals committed by the blob guest, one per blob
+    let journals: Vec<Vec<u8>> = env::read();
+
+    assert!(!journals.is_empty(), "No blob proofs to aggregate");
+
+    let blobs = journals
+        .iter()
+        .map(|journal| {
+            env::verify(image_id, journal).unwrap();
+            let output = Output::abi_decode(journal).expect("Invalid blob guest journal");
+            AggregatedBlob {
+                hash: output.hash,
+                env_commitment: output.env_commitment,
+                chain_id: output.chain_id,
+            }
+        })
+        .collect();
+
+    let output = AggregatedOutput {
+        image_id: Digest::from(image_id).as_bytes().to_vec(),
+        blobs,
+    };
+
+    env::commit_slice(&output.abi_encode());
+}
@@ -18,12 +18,16 @@
 use alloy_primitives::Address;
 use ark_bn254::Fr;
 use ark_serialize::CanonicalSerialize;
+use common::chain::Chain;
 use common::output::Output;
 use common::polynomial_form::PolynomialForm;
 use common::serializable_g1::SerializableG1;
 use common::verify_blob::IVerifyBlob;
 use risc0_steel::{
-    ethereum::{EthEvmInput, ETH_HOLESKY_CHAIN_SPEC},
+    ethereum::{
+        EthChainSpec, EthEvmInput, ETH_HOLESKY_CHAIN_SPEC, ETH_MAINNET_CHAIN_SPEC,
+        ETH_SEPOLIA_CHAIN_SPEC,
+    },
     Contract,
 };
 use risc0_zkvm::guest::env;
@@ -54,6 +58,19 @@ pub fn eval(coeffs: &[Fr], point: Fr) -> Fr {
     val
 }
 
+/// Steel chain spec of the chain, devnets run the latest mainnet hardfork since genesis.
+fn chain_spec(chain: Chain) -> EthChainSpec {
+    match chain {
+        Chain::Mainnet => ETH_MAINNET_CHAIN_SPEC.clone(),
+        Chain::Sepolia => ETH_SEPOLIA_CHAIN_SPEC.clone(),
+        Chain::Holesky => ETH_HOLESKY_CHAIN_SPEC.clone(),
+        Chain::Devnet(chain_id) => {
+            let latest_fork = *ETH_MAINNET_CHAIN_SPEC.forks.keys().last().unwrap();
+            EthChainSpec::new_single(chain_id, latest_fork)
+        }
+    }
+}
+
 /// This guest proves that an EigenDA Cert (BlobInfo) is valid, as well as that
 /// the cert's commitment corresponds to a given blob.
 /// It uses the risc0 Steel library to prove the cert validity via an eth_call on the BlobVerifierWrapper.
@@ -74,6 +91,8 @@ fn main() {
     let cert_verifier_router_addr: Address = env::read();
 
     let polynomial_form: PolynomialForm = env::read();
+    // Chain the cert is verified against, committed so the verifier can check it
+    let chain: Chain = env::read();
 
     let payload_form = match polynomial_form {
         PolynomialForm::Coeff => PayloadForm::Coeff,
@@ -84,7 +103,7 @@ fn main() {
     let blob = Blob::new(&payload.to_blob(payload_form).unwrap().serialize());
 
     // Converts the input into a `EvmEnv` for execution.
-    let env = input.into_env(&ETH_HOLESKY_CHAIN_SPEC);
+    let env = input.into_env(&chain_spec(chain));
 
     // Execute the view call; it returns the result in the type generated by the `sol!` macro.
     let contract = Contract::new(cert_verifier_router_addr, &env);
@@ -132,11 +151,12 @@ fn main() {
 
     let mut proof_bytes = vec![];
     proof.g1.serialize_compressed(&mut proof_bytes).unwrap();
-    // Public outputs of the guest, eigenDAHash, commitment to the risc0 steel environment and proof, they are embedded on the risc0 proof
+    // Public outputs of the guest, eigenDAHash, commitment to the risc0 steel environment, proof and chain ID, they are embedded on the risc0 proof
     let output = Output {
         hash: hash.to_vec(),
         env_commitment: env.commitment().abi_encode(),
         proof: proof_bytes,
+        chain_id: chain.chain_id(),
     };
 
     env::commit_slice(&output.abi_encode());